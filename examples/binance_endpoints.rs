@@ -1,8 +1,8 @@
 use binance::api::*;
 use binance::config::*;
-use binance::general::*;
-use binance::account::*;
-use binance::market::*;
+use binance::blocking::general::*;
+use binance::blocking::account::*;
+use binance::blocking::market::*;
 use binance::errors::ErrorKind as BinanceLibErrorKind;
 
 fn main() {
@@ -78,7 +78,7 @@ fn account() {
         Err(e) => println!("Error: {}", e),
     }
 
-    match account.limit_buy("WTCETH", 10, 0.014000) {
+    match account.limit_buy("WTCETH", 10, "0.014".parse().unwrap()) {
         Ok(answer) => println!("{:?}", answer),
         Err(e) => println!("Error: {}", e),
     }
@@ -93,7 +93,7 @@ fn account() {
         Err(e) => println!("Error: {}", e),
     }
 
-    match account.limit_sell("WTCETH", 10, 0.035000) {
+    match account.limit_sell("WTCETH", 10, "0.035".parse().unwrap()) {
         Ok(answer) => println!("{:?}", answer),
         Err(e) => println!("Error: {}", e),
     }
@@ -188,7 +188,7 @@ fn market_data() {
     }
 
     // last 10 5min klines (candlesticks) for a symbol:
-    match market.get_klines("BNBETH", "5m", 10, None, None) {
+    match market.get_klines("BNBETH", KlineInterval::Minute5, 10, None, None) {
         Ok(answer) => println!("{:?}", answer),
         Err(e) => println!("Error: {}", e),
     }
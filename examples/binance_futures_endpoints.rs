@@ -1,6 +1,6 @@
 use binance::api::*;
-use binance::futures::general::*;
-use binance::futures::market::*;
+use binance::blocking::futures::general::*;
+use binance::blocking::futures::market::*;
 use binance::futures::model::*;
 use binance::errors::ErrorKind as BinanceLibErrorKind;
 
@@ -61,7 +61,7 @@ fn market_data() {
         Err(e) => println!("Error: {}", e),
     }
 
-    match market.get_klines("btcusdt", "5m", 10, None, None) {
+    match market.get_klines("btcusdt", KlineInterval::Minute5, 10, None, None) {
         Ok(KlineSummaries::AllKlineSummaries(answer)) => println!("First kline: {:?}", answer[0]),
         Err(e) => println!("Error: {}", e),
     }
@@ -1,3 +1,6 @@
+// The websocket callbacks return the crate's `Result`
+#![allow(clippy::result_large_err)]
+
 use std::error::Error;
 use std::fs::File;
 use csv::Writer;
@@ -13,7 +16,7 @@ fn main() {
 fn save_all_trades_websocket() {
     struct WebSocketHandler {
         wrt: Writer<File>,
-    };
+    }
 
     impl WebSocketHandler {
         pub fn new(local_wrt: Writer<File>) -> Self {
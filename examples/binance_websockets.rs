@@ -1,5 +1,10 @@
+// Uncomment the examples to run in main
+#![allow(dead_code)]
+// The websocket callbacks return the crate's `Result`
+#![allow(clippy::result_large_err)]
+
 use binance::api::*;
-use binance::userstream::*;
+use binance::blocking::userstream::*;
 use binance::websockets::*;
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -45,8 +50,8 @@ fn user_stream_websocket() {
 
         let mut web_socket: WebSockets<'_> = WebSockets::new(|event: WebsocketEvent| {
             match event {
-                WebsocketEvent::AccountUpdate(account_update) => {
-                    for balance in &account_update.balance {
+                WebsocketEvent::AccountPosition(account_position) => {
+                    for balance in &account_position.balances {
                         println!(
                             "Asset: {}, free: {}, locked: {}",
                             balance.asset, balance.free, balance.locked
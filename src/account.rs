@@ -6,611 +6,332 @@ use std::collections::BTreeMap;
 use crate::api::API;
 use crate::api::Spot;
 
+pub use crate::model::{OrderSide, OrderType, TimeInForce};
+
 #[derive(Clone)]
 pub struct Account {
     pub client: Client,
     pub recv_window: u64,
 }
 
-struct OrderRequest {
+pub(crate) struct OrderQuantityRequest {
     pub symbol: String,
-    pub qty: f64,
-    pub price: f64,
-    pub stop_price: Option<f64>,
+    pub qty: Amount,
+    pub price: Amount,
+    pub stop_price: Option<Amount>,
     pub order_side: OrderSide,
     pub order_type: OrderType,
     pub time_in_force: TimeInForce,
 }
 
-struct OrderQuoteQuantityRequest {
+pub(crate) struct OrderQuoteQuantityRequest {
     pub symbol: String,
-    pub quote_order_qty: f64,
-    pub price: f64,
+    pub quote_order_qty: Amount,
+    pub price: Amount,
     pub order_side: OrderSide,
     pub order_type: OrderType,
     pub time_in_force: TimeInForce,
 }
 
-pub enum OrderType {
-    Limit,
-    Market,
-    StopLossLimit,
-}
-
-impl From<OrderType> for String {
-    fn from(item: OrderType) -> Self {
-        match item {
-            OrderType::Limit => String::from("LIMIT"),
-            OrderType::Market => String::from("MARKET"),
-            OrderType::StopLossLimit => String::from("STOP_LOSS_LIMIT"),
-        }
-    }
+pub(crate) struct OcoRequest {
+    pub symbol: String,
+    pub qty: Amount,
+    pub price: Amount,
+    pub stop_price: Amount,
+    pub stop_limit_price: Option<Amount>,
+    pub order_side: OrderSide,
 }
 
-pub enum OrderSide {
-    Buy,
-    Sell,
+/// Set with `newOrderRespType`. Binance defaults to `Full` for MARKET and LIMIT orders
+/// and to `Ack` for the other types.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderResponseType {
+    Ack,
+    Result,
+    Full,
 }
 
-impl From<OrderSide> for String {
-    fn from(item: OrderSide) -> Self {
+impl From<OrderResponseType> for String {
+    fn from(item: OrderResponseType) -> Self {
         match item {
-            OrderSide::Buy => String::from("BUY"),
-            OrderSide::Sell => String::from("SELL"),
+            OrderResponseType::Ack => String::from("ACK"),
+            OrderResponseType::Result => String::from("RESULT"),
+            OrderResponseType::Full => String::from("FULL"),
         }
     }
 }
 
-#[allow(clippy::all)]
-pub enum TimeInForce {
-    GTC,
-    IOC,
-    FOK,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelfTradePreventionMode {
+    ExpireTaker,
+    ExpireMaker,
+    ExpireBoth,
+    None,
 }
 
-impl From<TimeInForce> for String {
-    fn from(item: TimeInForce) -> Self {
+impl From<SelfTradePreventionMode> for String {
+    fn from(item: SelfTradePreventionMode) -> Self {
         match item {
-            TimeInForce::GTC => String::from("GTC"),
-            TimeInForce::IOC => String::from("IOC"),
-            TimeInForce::FOK => String::from("FOK"),
+            SelfTradePreventionMode::ExpireTaker => String::from("EXPIRE_TAKER"),
+            SelfTradePreventionMode::ExpireMaker => String::from("EXPIRE_MAKER"),
+            SelfTradePreventionMode::ExpireBoth => String::from("EXPIRE_BOTH"),
+            SelfTradePreventionMode::None => String::from("NONE"),
         }
     }
 }
 
-#[cfg(feature = "blocking")]
-impl Account {
-    // Account Information
-    pub fn get_account(&self) -> Result<AccountInformation> {
-        let request = build_signed_request(BTreeMap::new(), self.recv_window)?;
-        self.client
-            .get_signed(API::Spot(Spot::Account), Some(request))
-    }
-
-    // Balance for ONE Asset
-    pub fn get_balance<S>(&self, asset: S) -> Result<Balance>
-    where
-        S: Into<String>,
-    {
-        match self.get_account() {
-            Ok(account) => {
-                let cmp_asset = asset.into();
-                for balance in account.balances {
-                    if balance.asset == cmp_asset {
-                        return Ok(balance);
-                    }
-                }
-                bail!("Asset not found");
-            }
-            Err(e) => Err(e),
-        }
-    }
-
-    // Current open orders for ONE symbol
-    pub fn get_open_orders<S>(&self, symbol: S) -> Result<Vec<Order>>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-
-        let request = build_signed_request(parameters, self.recv_window)?;
-        self.client
-            .get_signed(API::Spot(Spot::OpenOrders), Some(request))
-    }
-
-    // All current open orders
-    pub fn get_all_open_orders(&self) -> Result<Vec<Order>> {
-        let parameters: BTreeMap<String, String> = BTreeMap::new();
-
-        let request = build_signed_request(parameters, self.recv_window)?;
-        self.client
-            .get_signed(API::Spot(Spot::OpenOrders), Some(request))
-    }
-
-    // Cancel all open orders for ONE symbol
-    pub fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<Vec<OrderCanceled>>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        let request = build_signed_request(parameters, self.recv_window)?;
-        self.client
-            .delete_signed(API::Spot(Spot::OpenOrders), Some(request))
-    }
-
-    // Check an order's status
-    pub fn order_status<S>(&self, symbol: S, order_id: u64) -> Result<Order>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("orderId".into(), order_id.to_string());
-
-        let request = build_signed_request(parameters, self.recv_window)?;
-        self.client
-            .get_signed(API::Spot(Spot::Order), Some(request))
-    }
-
-    /// Place a test status order
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_order_status<S>(&self, symbol: S, order_id: u64) -> Result<()>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("orderId".into(), order_id.to_string());
-
-        let request = build_signed_request(parameters, self.recv_window)?;
-        self.client
-            .get_signed::<()>(API::Spot(Spot::OrderTest), Some(request))
-    }
-
-    // Place a LIMIT order - BUY
-    pub fn limit_buy<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<Transaction>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let buy: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price,
-            stop_price: None,
-            order_side: OrderSide::Buy,
-            order_type: OrderType::Limit,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_order(buy);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client.post_signed(API::Spot(Spot::Order), request)
-    }
-
-    /// Place a test limit order - BUY
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_limit_buy<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<()>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let buy: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price,
-            stop_price: None,
-            order_side: OrderSide::Buy,
-            order_type: OrderType::Limit,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_order(buy);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client
-            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
-    }
-
-    // Place a LIMIT order - SELL
-    pub fn limit_sell<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<Transaction>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price,
-            stop_price: None,
-            order_side: OrderSide::Sell,
-            order_type: OrderType::Limit,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client.post_signed(API::Spot(Spot::Order), request)
-    }
-
-    /// Place a test LIMIT order - SELL
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_limit_sell<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<()>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price,
-            stop_price: None,
-            order_side: OrderSide::Sell,
-            order_type: OrderType::Limit,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client
-            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
-    }
-
-    // Place a MARKET order - BUY
-    pub fn market_buy<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let buy: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price: 0.0,
-            stop_price: None,
-            order_side: OrderSide::Buy,
-            order_type: OrderType::Market,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_order(buy);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client.post_signed(API::Spot(Spot::Order), request)
-    }
-
-    /// Place a test MARKET order - BUY
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_market_buy<S, F>(&self, symbol: S, qty: F) -> Result<()>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let buy: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price: 0.0,
-            stop_price: None,
-            order_side: OrderSide::Buy,
-            order_type: OrderType::Market,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_order(buy);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client
-            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
-    }
-
-    // Place a MARKET order with quote quantity - BUY
-    pub fn market_buy_using_quote_quantity<S, F>(
-        &self, symbol: S, quote_order_qty: F,
-    ) -> Result<Transaction>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
-            symbol: symbol.into(),
-            quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
-            order_side: OrderSide::Buy,
-            order_type: OrderType::Market,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_quote_quantity_order(buy);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client.post_signed(API::Spot(Spot::Order), request)
-    }
-
-    /// Place a test MARKET order with quote quantity - BUY
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_market_buy_using_quote_quantity<S, F>(
-        &self, symbol: S, quote_order_qty: F,
-    ) -> Result<()>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
-            symbol: symbol.into(),
-            quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
-            order_side: OrderSide::Buy,
-            order_type: OrderType::Market,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_quote_quantity_order(buy);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client
-            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
-    }
-
-    // Place a MARKET order - SELL
-    pub fn market_sell<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price: 0.0,
-            stop_price: None,
-            order_side: OrderSide::Sell,
-            order_type: OrderType::Market,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client.post_signed(API::Spot(Spot::Order), request)
-    }
-
-    /// Place a test MARKET order - SELL
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_market_sell<S, F>(&self, symbol: S, qty: F) -> Result<()>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price: 0.0,
-            stop_price: None,
-            order_side: OrderSide::Sell,
-            order_type: OrderType::Market,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client
-            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
-    }
-
-    // Place a MARKET order with quote quantity - SELL
-    pub fn market_sell_using_quote_quantity<S, F>(
-        &self, symbol: S, quote_order_qty: F,
-    ) -> Result<Transaction>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
-            symbol: symbol.into(),
-            quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
-            order_side: OrderSide::Sell,
-            order_type: OrderType::Market,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_quote_quantity_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client.post_signed(API::Spot(Spot::Order), request)
-    }
-
-    /// Place a test MARKET order with quote quantity - SELL
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_market_sell_using_quote_quantity<S, F>(
-        &self, symbol: S, quote_order_qty: F,
-    ) -> Result<()>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
-            symbol: symbol.into(),
-            quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
-            order_side: OrderSide::Sell,
-            order_type: OrderType::Market,
-            time_in_force: TimeInForce::GTC,
-        };
-        let order = self.build_quote_quantity_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client
-            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
-    }
-
-    /// Place a stop limit buy order
-    pub fn stop_limit_buy_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
-    ) -> Result<Transaction>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price,
-            stop_price: Some(stop_price),
-            order_side: OrderSide::Buy,
-            order_type: OrderType::StopLossLimit,
-            time_in_force,
-        };
-        let order = self.build_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client.post_signed(API::Spot(Spot::Order), request)
-    }
-
-    /// Place a test stop limit buy order
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_stop_limit_buy_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
-    ) -> Result<()>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price,
-            stop_price: Some(stop_price),
-            order_side: OrderSide::Buy,
-            order_type: OrderType::StopLossLimit,
-            time_in_force,
-        };
-        let order = self.build_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client
-            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+/// What `cancel_replace_order` does when cancelling the existing order fails.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CancelReplaceMode {
+    /// Do not place the new order
+    StopOnFailure,
+    /// Place the new order anyway
+    AllowFailure,
+}
+
+impl From<CancelReplaceMode> for String {
+    fn from(item: CancelReplaceMode) -> Self {
+        match item {
+            CancelReplaceMode::StopOnFailure => String::from("STOP_ON_FAILURE"),
+            CancelReplaceMode::AllowFailure => String::from("ALLOW_FAILURE"),
+        }
     }
+}
 
-    /// Place a custom order
-    #[allow(clippy::too_many_arguments)]
-    pub fn custom_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: Option<f64>, order_side: OrderSide,
-        order_type: OrderType, time_in_force: TimeInForce,
-    ) -> Result<Transaction>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderRequest = OrderRequest {
+/// A spot order with every parameter of `POST /api/v3/order`.
+///
+/// The parameters required by the order type are checked before anything is sent:
+///
+/// - `Limit`: time in force, quantity, price
+/// - `Market`: quantity or quote order quantity
+/// - `StopLoss`, `TakeProfit`: quantity, stop price or trailing delta
+/// - `StopLossLimit`, `TakeProfitLimit`: time in force, quantity, price, stop price or
+///   trailing delta
+/// - `LimitMaker`: quantity, price
+///
+/// Amounts are parsed from strings here, which works with and without the `decimal`
+/// feature.
+///
+/// ```rust,no_run
+/// use binance::account::*;
+///
+/// let order = OrderRequest::new("BNBBTC", OrderSide::Buy, OrderType::Limit)
+///     .set_time_in_force(TimeInForce::GTC)
+///     .set_quantity("1".parse().unwrap())
+///     .set_price("0.0021".parse().unwrap())
+///     .set_new_client_order_id("my-order-1");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Option<Amount>,
+    pub quote_order_qty: Option<Amount>,
+    pub price: Option<Amount>,
+    pub new_client_order_id: Option<String>,
+    pub stop_price: Option<Amount>,
+    pub trailing_delta: Option<u64>,
+    pub iceberg_qty: Option<Amount>,
+    pub new_order_resp_type: Option<OrderResponseType>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+}
+
+impl OrderRequest {
+    pub fn new<S: Into<String>>(symbol: S, side: OrderSide, order_type: OrderType) -> Self {
+        OrderRequest {
             symbol: symbol.into(),
-            qty: qty.into(),
-            price,
-            stop_price,
-            order_side,
+            side,
             order_type,
-            time_in_force,
-        };
-        let order = self.build_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client.post_signed(API::Spot(Spot::Order), request)
+            time_in_force: None,
+            quantity: None,
+            quote_order_qty: None,
+            price: None,
+            new_client_order_id: None,
+            stop_price: None,
+            trailing_delta: None,
+            iceberg_qty: None,
+            new_order_resp_type: None,
+            self_trade_prevention_mode: None,
+        }
     }
 
-    /// Place a test custom order
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_custom_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, order_side: OrderSide, order_type: OrderType,
-        time_in_force: TimeInForce,
-    ) -> Result<()>
-    where
-        S: Into<String>,
-        F: Into<f64>,
-    {
-        let sell: OrderRequest = OrderRequest {
-            symbol: symbol.into(),
-            qty: qty.into(),
-            price,
-            stop_price: None,
-            order_side,
-            order_type,
-            time_in_force,
-        };
-        let order = self.build_order(sell);
-        let request = build_signed_request(order, self.recv_window)?;
-        self.client
-            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+    pub fn set_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
     }
 
-    // Check an order's status
-    pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<OrderCanceled>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("orderId".into(), order_id.to_string());
+    pub fn set_quantity(mut self, quantity: Amount) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
 
-        let request = build_signed_request(parameters, self.recv_window)?;
-        self.client
-            .delete_signed(API::Spot(Spot::Order), Some(request))
+    pub fn set_quote_order_qty(mut self, quote_order_qty: Amount) -> Self {
+        self.quote_order_qty = Some(quote_order_qty);
+        self
     }
 
-    /// Place a test cancel order
-    ///
-    /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<()>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("orderId".into(), order_id.to_string());
-        let request = build_signed_request(parameters, self.recv_window)?;
-        self.client
-            .delete_signed::<()>(API::Spot(Spot::OrderTest), Some(request))
+    pub fn set_price(mut self, price: Amount) -> Self {
+        self.price = Some(price);
+        self
     }
 
-    // Trade history
-    pub fn trade_history<S>(&self, symbol: S) -> Result<Vec<TradeHistory>>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
+    pub fn set_new_client_order_id<S: Into<String>>(mut self, new_client_order_id: S) -> Self {
+        self.new_client_order_id = Some(new_client_order_id.into());
+        self
+    }
 
-        let request = build_signed_request(parameters, self.recv_window)?;
-        self.client
-            .get_signed(API::Spot(Spot::MyTrades), Some(request))
+    pub fn set_stop_price(mut self, stop_price: Amount) -> Self {
+        self.stop_price = Some(stop_price);
+        self
     }
 
-    fn build_order(&self, order: OrderRequest) -> BTreeMap<String, String> {
-        let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
+    /// Trailing stop distance in basis points (BIPS).
+    pub fn set_trailing_delta(mut self, trailing_delta: u64) -> Self {
+        self.trailing_delta = Some(trailing_delta);
+        self
+    }
 
-        order_parameters.insert("symbol".into(), order.symbol);
-        order_parameters.insert("side".into(), order.order_side.into());
-        order_parameters.insert("type".into(), order.order_type.into());
-        order_parameters.insert("quantity".into(), order.qty.to_string());
+    pub fn set_iceberg_qty(mut self, iceberg_qty: Amount) -> Self {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
 
-        if let Some(stop_price) = order.stop_price {
-            order_parameters.insert("stopPrice".into(), stop_price.to_string());
-        }
+    pub fn set_new_order_resp_type(mut self, new_order_resp_type: OrderResponseType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn set_self_trade_prevention_mode(
+        mut self, self_trade_prevention_mode: SelfTradePreventionMode,
+    ) -> Self {
+        self.self_trade_prevention_mode = Some(self_trade_prevention_mode);
+        self
+    }
 
-        if order.price != 0.0 {
-            order_parameters.insert("price".into(), order.price.to_string());
-            order_parameters.insert("timeInForce".into(), order.time_in_force.into());
+    pub fn validate(&self) -> Result<()> {
+        let limit_price = matches!(
+            self.order_type,
+            OrderType::Limit
+                | OrderType::StopLossLimit
+                | OrderType::TakeProfitLimit
+                | OrderType::LimitMaker
+        );
+        let time_in_force = matches!(
+            self.order_type,
+            OrderType::Limit | OrderType::StopLossLimit | OrderType::TakeProfitLimit
+        );
+        // Binance answers -1106 when these are sent a price or time in force
+        let market_price = matches!(
+            self.order_type,
+            OrderType::Market | OrderType::StopLoss | OrderType::TakeProfit
+        );
+        let trigger = matches!(
+            self.order_type,
+            OrderType::StopLoss
+                | OrderType::StopLossLimit
+                | OrderType::TakeProfit
+                | OrderType::TakeProfitLimit
+        );
+
+        if self.order_type == OrderType::Market {
+            if self.quantity.is_some() == self.quote_order_qty.is_some() {
+                bail!("MARKET orders need either a quantity or a quote order quantity");
+            }
+        } else {
+            if self.quantity.is_none() {
+                bail!("{} orders need a quantity", self.order_type);
+            }
+            if self.quote_order_qty.is_some() {
+                bail!("Quote order quantity is only valid for MARKET orders");
+            }
+        }
+        if limit_price && self.price.is_none() {
+            bail!("{} orders need a price", self.order_type);
+        }
+        if market_price && self.price.is_some() {
+            bail!("Price is not valid for {} orders", self.order_type);
+        }
+        if time_in_force && self.time_in_force.is_none() {
+            bail!("{} orders need a time in force", self.order_type);
+        }
+        if (market_price || self.order_type == OrderType::LimitMaker)
+            && self.time_in_force.is_some()
+        {
+            bail!("Time in force is not valid for {} orders", self.order_type);
+        }
+        if trigger && self.stop_price.is_none() && self.trailing_delta.is_none() {
+            bail!(
+                "{} orders need a stop price or a trailing delta",
+                self.order_type
+            );
+        }
+        if !trigger && (self.stop_price.is_some() || self.trailing_delta.is_some()) {
+            bail!(
+                "Stop price and trailing delta are not valid for {} orders",
+                self.order_type
+            );
+        }
+        if self.iceberg_qty.is_some() {
+            if !limit_price {
+                bail!("Iceberg quantity is only valid for limit orders");
+            }
+            if time_in_force && self.time_in_force != Some(TimeInForce::GTC) {
+                bail!("Iceberg orders must be GTC");
+            }
         }
 
-        order_parameters
+        Ok(())
     }
 
-    fn build_quote_quantity_order(
-        &self, order: OrderQuoteQuantityRequest,
-    ) -> BTreeMap<String, String> {
+    pub(crate) fn build(self) -> Result<BTreeMap<String, String>> {
+        self.validate()?;
+
         let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
 
-        order_parameters.insert("symbol".into(), order.symbol);
-        order_parameters.insert("side".into(), order.order_side.into());
-        order_parameters.insert("type".into(), order.order_type.into());
-        order_parameters.insert("quoteOrderQty".into(), order.quote_order_qty.to_string());
+        order_parameters.insert("symbol".into(), self.symbol);
+        order_parameters.insert("side".into(), self.side.into());
+        order_parameters.insert("type".into(), self.order_type.into());
 
-        if order.price != 0.0 {
-            order_parameters.insert("price".into(), order.price.to_string());
-            order_parameters.insert("timeInForce".into(), order.time_in_force.into());
+        if let Some(time_in_force) = self.time_in_force {
+            order_parameters.insert("timeInForce".into(), time_in_force.into());
+        }
+        if let Some(quantity) = self.quantity {
+            order_parameters.insert("quantity".into(), quantity.to_string());
+        }
+        if let Some(quote_order_qty) = self.quote_order_qty {
+            order_parameters.insert("quoteOrderQty".into(), quote_order_qty.to_string());
+        }
+        if let Some(price) = self.price {
+            order_parameters.insert("price".into(), price.to_string());
+        }
+        if let Some(new_client_order_id) = self.new_client_order_id {
+            order_parameters.insert("newClientOrderId".into(), new_client_order_id);
+        }
+        if let Some(stop_price) = self.stop_price {
+            order_parameters.insert("stopPrice".into(), stop_price.to_string());
+        }
+        if let Some(trailing_delta) = self.trailing_delta {
+            order_parameters.insert("trailingDelta".into(), trailing_delta.to_string());
+        }
+        if let Some(iceberg_qty) = self.iceberg_qty {
+            order_parameters.insert("icebergQty".into(), iceberg_qty.to_string());
+        }
+        if let Some(new_order_resp_type) = self.new_order_resp_type {
+            order_parameters.insert("newOrderRespType".into(), new_order_resp_type.into());
+        }
+        if let Some(self_trade_prevention_mode) = self.self_trade_prevention_mode {
+            order_parameters.insert(
+                "selfTradePreventionMode".into(),
+                self_trade_prevention_mode.into(),
+            );
         }
 
-        order_parameters
+        Ok(order_parameters)
     }
 }
 
-#[cfg(not(feature = "blocking"))]
 impl Account {
     // Account Information
     pub async fn get_account(&self) -> Result<AccountInformation> {
@@ -663,6 +384,41 @@ impl Account {
             .await
     }
 
+    // All orders for ONE symbol: active, canceled or filled
+    //
+    // From `order_id` onwards when set, otherwise the most recent ones
+    pub async fn get_all_orders<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, order_id: S2, start_time: S3, end_time: S4, limit: S5,
+    ) -> Result<Vec<Order>>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        if let Some(order_id) = order_id.into() {
+            parameters.insert("orderId".into(), order_id.to_string());
+        }
+        if let Some(start_time) = start_time.into() {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = end_time.into() {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(limit) = limit.into() {
+            parameters.insert("limit".into(), limit.to_string());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::AllOrders), Some(request))
+            .await
+    }
+
     // Cancel all open orders for ONE symbol
     pub async fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<Vec<OrderCanceled>>
     where
@@ -691,6 +447,24 @@ impl Account {
             .await
     }
 
+    // Check an order's status by the client order id it was placed with
+    pub async fn order_status_with_client_id<S1, S2>(
+        &self, symbol: S1, orig_client_order_id: S2,
+    ) -> Result<Order>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("origClientOrderId".into(), orig_client_order_id.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::Order), Some(request))
+            .await
+    }
+
     /// Place a test status order
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
@@ -709,12 +483,12 @@ impl Account {
     }
 
     // Place a LIMIT order - BUY
-    pub async fn limit_buy<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<Transaction>
+    pub async fn limit_buy<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let buy: OrderRequest = OrderRequest {
+        let buy: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
             price,
@@ -723,7 +497,7 @@ impl Account {
             order_type: OrderType::Limit,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Spot(Spot::Order), request)
@@ -733,12 +507,12 @@ impl Account {
     /// Place a test limit order - BUY
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub async fn test_limit_buy<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<()>
+    pub async fn test_limit_buy<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let buy: OrderRequest = OrderRequest {
+        let buy: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
             price,
@@ -747,7 +521,7 @@ impl Account {
             order_type: OrderType::Limit,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<()>(API::Spot(Spot::OrderTest), request)
@@ -755,12 +529,12 @@ impl Account {
     }
 
     // Place a LIMIT order - SELL
-    pub async fn limit_sell<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<Transaction>
+    pub async fn limit_sell<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let sell: OrderRequest = OrderRequest {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
             price,
@@ -769,7 +543,7 @@ impl Account {
             order_type: OrderType::Limit,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Spot(Spot::Order), request)
@@ -779,12 +553,12 @@ impl Account {
     /// Place a test LIMIT order - SELL
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub async fn test_limit_sell<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<()>
+    pub async fn test_limit_sell<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let sell: OrderRequest = OrderRequest {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
             price,
@@ -793,7 +567,7 @@ impl Account {
             order_type: OrderType::Limit,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<()>(API::Spot(Spot::OrderTest), request)
@@ -804,18 +578,18 @@ impl Account {
     pub async fn market_buy<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let buy: OrderRequest = OrderRequest {
+        let buy: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: ZERO,
             stop_price: None,
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Spot(Spot::Order), request)
@@ -828,18 +602,18 @@ impl Account {
     pub async fn test_market_buy<S, F>(&self, symbol: S, qty: F) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let buy: OrderRequest = OrderRequest {
+        let buy: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: ZERO,
             stop_price: None,
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<()>(API::Spot(Spot::OrderTest), request)
@@ -852,17 +626,17 @@ impl Account {
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: ZERO,
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_quote_quantity_order(buy);
+        let order = build_quote_quantity_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Spot(Spot::Order), request)
@@ -877,17 +651,17 @@ impl Account {
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: ZERO,
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_quote_quantity_order(buy);
+        let order = build_quote_quantity_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<()>(API::Spot(Spot::OrderTest), request)
@@ -898,18 +672,18 @@ impl Account {
     pub async fn market_sell<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let sell: OrderRequest = OrderRequest {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: ZERO,
             stop_price: None,
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Spot(Spot::Order), request)
@@ -922,18 +696,18 @@ impl Account {
     pub async fn test_market_sell<S, F>(&self, symbol: S, qty: F) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let sell: OrderRequest = OrderRequest {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: ZERO,
             stop_price: None,
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<()>(API::Spot(Spot::OrderTest), request)
@@ -946,17 +720,17 @@ impl Account {
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: ZERO,
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_quote_quantity_order(sell);
+        let order = build_quote_quantity_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Spot(Spot::Order), request)
@@ -971,17 +745,17 @@ impl Account {
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
         let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: ZERO,
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
         };
-        let order = self.build_quote_quantity_order(sell);
+        let order = build_quote_quantity_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<()>(API::Spot(Spot::OrderTest), request)
@@ -990,13 +764,13 @@ impl Account {
 
     /// Place a stop limit buy order
     pub async fn stop_limit_buy_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, time_in_force: TimeInForce,
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let sell: OrderRequest = OrderRequest {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
             price,
@@ -1005,7 +779,7 @@ impl Account {
             order_type: OrderType::StopLossLimit,
             time_in_force,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Spot(Spot::Order), request)
@@ -1016,13 +790,13 @@ impl Account {
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
     pub async fn test_stop_limit_buy_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, time_in_force: TimeInForce,
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let sell: OrderRequest = OrderRequest {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
             price,
@@ -1031,7 +805,7 @@ impl Account {
             order_type: OrderType::StopLossLimit,
             time_in_force,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<()>(API::Spot(Spot::OrderTest), request)
@@ -1041,14 +815,14 @@ impl Account {
     /// Place a custom order
     #[allow(clippy::too_many_arguments)]
     pub async fn custom_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: Option<f64>, order_side: OrderSide,
+        &self, symbol: S, qty: F, price: Amount, stop_price: Option<Amount>, order_side: OrderSide,
         order_type: OrderType, time_in_force: TimeInForce,
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let sell: OrderRequest = OrderRequest {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
             price,
@@ -1057,7 +831,7 @@ impl Account {
             order_type,
             time_in_force,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Spot(Spot::Order), request)
@@ -1068,14 +842,14 @@ impl Account {
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
     pub async fn test_custom_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, order_side: OrderSide, order_type: OrderType,
+        &self, symbol: S, qty: F, price: Amount, order_side: OrderSide, order_type: OrderType,
         time_in_force: TimeInForce,
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Amount>,
     {
-        let sell: OrderRequest = OrderRequest {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
             symbol: symbol.into(),
             qty: qty.into(),
             price,
@@ -1084,13 +858,37 @@ impl Account {
             order_type,
             time_in_force,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<()>(API::Spot(Spot::OrderTest), request)
             .await
     }
 
+    /// Place any spot order built with `OrderRequest`
+    ///
+    /// The request is validated first, so missing parameters fail without a round trip.
+    pub async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse> {
+        let order = order.build()?;
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Spot(Spot::Order), request)
+            .await
+    }
+
+    /// Place a test order built with `OrderRequest`
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub async fn test_order(&self, order: OrderRequest) -> Result<()> {
+        let order = order.build()?;
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<TestResponse>(API::Spot(Spot::OrderTest), request)
+            .await?;
+
+        Ok(())
+    }
+
     // Check an order's status
     pub async fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<OrderCanceled>
     where
@@ -1106,6 +904,74 @@ impl Account {
             .await
     }
 
+    // Cancel an order by the client order id it was placed with
+    //
+    // `new_client_order_id` identifies the cancellation itself
+    pub async fn cancel_order_with_client_id<S1, S2>(
+        &self, symbol: S1, orig_client_order_id: S2, new_client_order_id: Option<&str>,
+    ) -> Result<OrderCanceled>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("origClientOrderId".into(), orig_client_order_id.into());
+
+        if let Some(new_client_order_id) = new_client_order_id {
+            parameters.insert("newClientOrderId".into(), new_client_order_id.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Spot(Spot::Order), Some(request))
+            .await
+    }
+
+    /// Cancel an existing order and place a new one in a single request
+    ///
+    /// The new order is placed on the symbol of `order`. When either step fails, the
+    /// returned `CancelReplaceResponse` holds the error of that step instead of
+    /// `Err`, so check `cancel_result` and `new_order_result`.
+    pub async fn cancel_replace_order(
+        &self, cancel_order_id: u64, order: OrderRequest, mode: CancelReplaceMode,
+    ) -> Result<CancelReplaceResponse> {
+        let mut parameters = order.build()?;
+        parameters.insert("cancelOrderId".into(), cancel_order_id.to_string());
+        parameters.insert("cancelReplaceMode".into(), mode.into());
+
+        self.cancel_replace(parameters).await
+    }
+
+    /// Cancel an existing order by client order id and place a new one in a single request
+    pub async fn cancel_replace_order_with_client_id<S>(
+        &self, cancel_orig_client_order_id: S, order: OrderRequest, mode: CancelReplaceMode,
+    ) -> Result<CancelReplaceResponse>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = order.build()?;
+        parameters.insert(
+            "cancelOrigClientOrderId".into(),
+            cancel_orig_client_order_id.into(),
+        );
+        parameters.insert("cancelReplaceMode".into(), mode.into());
+
+        self.cancel_replace(parameters).await
+    }
+
+    async fn cancel_replace(
+        &self, parameters: BTreeMap<String, String>,
+    ) -> Result<CancelReplaceResponse> {
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let result = self
+            .client
+            .post_signed(API::Spot(Spot::CancelReplace), request)
+            .await;
+
+        cancel_replace_result(result)
+    }
+
     /// Place a test cancel order
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
@@ -1122,6 +988,126 @@ impl Account {
             .await
     }
 
+    /// Place an OCO order - BUY
+    ///
+    /// A LIMIT_MAKER at `price` below the market and a stop at `stop_price` above it.
+    /// With `stop_limit_price` the stop is a STOP_LOSS_LIMIT order (GTC), otherwise a
+    /// STOP_LOSS market order.
+    pub async fn oco_buy<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount,
+        stop_limit_price: Option<Amount>,
+    ) -> Result<OrderList>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let buy = OcoRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price,
+            stop_limit_price,
+            order_side: OrderSide::Buy,
+        };
+        let order = build_oco_order(buy);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Oco), request).await
+    }
+
+    /// Place an OCO order - SELL
+    ///
+    /// A LIMIT_MAKER take-profit at `price` and a stop-loss at `stop_price`.
+    /// With `stop_limit_price` the stop is a STOP_LOSS_LIMIT order (GTC), otherwise a
+    /// STOP_LOSS market order.
+    pub async fn oco_sell<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount,
+        stop_limit_price: Option<Amount>,
+    ) -> Result<OrderList>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell = OcoRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price,
+            stop_limit_price,
+            order_side: OrderSide::Sell,
+        };
+        let order = build_oco_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Oco), request).await
+    }
+
+    // Cancel all orders of an order list
+    pub async fn cancel_order_list<S>(&self, symbol: S, order_list_id: u64) -> Result<OrderList>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderListId".into(), order_list_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Spot(Spot::OrderList), Some(request))
+            .await
+    }
+
+    // Check an order list's status
+    pub async fn order_list_status(&self, order_list_id: u64) -> Result<OrderList> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("orderListId".into(), order_list_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::OrderList), Some(request))
+            .await
+    }
+
+    // Order lists from `from_id` on or within the given times, up to `limit` (500 by
+    // default) of them
+    pub async fn get_all_order_lists<S1, S2, S3, S4>(
+        &self, from_id: S1, start_time: S2, end_time: S3, limit: S4,
+    ) -> Result<Vec<OrderList>>
+    where
+        S1: Into<Option<u64>>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(from_id) = from_id.into() {
+            parameters.insert("fromId".into(), from_id.to_string());
+        }
+        if let Some(start_time) = start_time.into() {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = end_time.into() {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(limit) = limit.into() {
+            parameters.insert("limit".into(), limit.to_string());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::AllOrderList), Some(request))
+            .await
+    }
+
+    // All open order lists
+    pub async fn get_open_order_lists(&self) -> Result<Vec<OrderList>> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::OpenOrderList), Some(request))
+            .await
+    }
+
     // Trade history
     pub async fn trade_history<S>(&self, symbol: S) -> Result<Vec<TradeHistory>>
     where
@@ -1135,42 +1121,89 @@ impl Account {
             .get_signed(API::Spot(Spot::MyTrades), Some(request))
             .await
     }
+}
 
-    fn build_order(&self, order: OrderRequest) -> BTreeMap<String, String> {
-        let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
+// Binance reports a failed cancel or new order with an error whose `data` holds the
+// outcome of both steps
+pub(crate) fn cancel_replace_result(
+    result: Result<CancelReplaceResponse>,
+) -> Result<CancelReplaceResponse> {
+    if let Err(Error(ErrorKind::BinanceError(ref response), _)) = result {
+        if let Some(data) = response.data() {
+            if let Ok(response) = serde_json::from_value(data.clone()) {
+                return Ok(response);
+            }
+        }
+    }
 
-        order_parameters.insert("symbol".into(), order.symbol);
-        order_parameters.insert("side".into(), order.order_side.into());
-        order_parameters.insert("type".into(), order.order_type.into());
-        order_parameters.insert("quantity".into(), order.qty.to_string());
+    result
+}
 
-        if let Some(stop_price) = order.stop_price {
-            order_parameters.insert("stopPrice".into(), stop_price.to_string());
-        }
+pub(crate) fn build_order(order: OrderQuantityRequest) -> BTreeMap<String, String> {
+    let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
 
-        if order.price != 0.0 {
-            order_parameters.insert("price".into(), order.price.to_string());
-            order_parameters.insert("timeInForce".into(), order.time_in_force.into());
-        }
+    order_parameters.insert("symbol".into(), order.symbol);
+    order_parameters.insert("side".into(), order.order_side.into());
+    order_parameters.insert("type".into(), order.order_type.into());
+    order_parameters.insert("quantity".into(), order.qty.to_string());
 
-        order_parameters
+    if let Some(stop_price) = order.stop_price {
+        order_parameters.insert("stopPrice".into(), stop_price.to_string());
     }
 
-    fn build_quote_quantity_order(
-        &self, order: OrderQuoteQuantityRequest,
-    ) -> BTreeMap<String, String> {
-        let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
+    if order.price != ZERO {
+        order_parameters.insert("price".into(), order.price.to_string());
+        order_parameters.insert("timeInForce".into(), order.time_in_force.into());
+    }
 
-        order_parameters.insert("symbol".into(), order.symbol);
-        order_parameters.insert("side".into(), order.order_side.into());
-        order_parameters.insert("type".into(), order.order_type.into());
-        order_parameters.insert("quoteOrderQty".into(), order.quote_order_qty.to_string());
+    order_parameters
+}
 
-        if order.price != 0.0 {
-            order_parameters.insert("price".into(), order.price.to_string());
-            order_parameters.insert("timeInForce".into(), order.time_in_force.into());
-        }
+pub(crate) fn build_quote_quantity_order(
+    order: OrderQuoteQuantityRequest,
+) -> BTreeMap<String, String> {
+    let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
+
+    order_parameters.insert("symbol".into(), order.symbol);
+    order_parameters.insert("side".into(), order.order_side.into());
+    order_parameters.insert("type".into(), order.order_type.into());
+    order_parameters.insert("quoteOrderQty".into(), order.quote_order_qty.to_string());
+
+    if order.price != ZERO {
+        order_parameters.insert("price".into(), order.price.to_string());
+        order_parameters.insert("timeInForce".into(), order.time_in_force.into());
+    }
+
+    order_parameters
+}
 
-        order_parameters
+// The LIMIT_MAKER at `price` is the leg above the market when selling and the one
+// below it when buying, the stop is on the other side
+pub(crate) fn build_oco_order(order: OcoRequest) -> BTreeMap<String, String> {
+    let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
+
+    let (limit_leg, stop_leg) = match order.order_side {
+        OrderSide::Buy => ("below", "above"),
+        _ => ("above", "below"),
+    };
+
+    order_parameters.insert("symbol".into(), order.symbol);
+    order_parameters.insert("side".into(), order.order_side.into());
+    order_parameters.insert("quantity".into(), order.qty.to_string());
+    order_parameters.insert(format!("{}Type", limit_leg), OrderType::LimitMaker.into());
+    order_parameters.insert(format!("{}Price", limit_leg), order.price.to_string());
+    order_parameters.insert(
+        format!("{}StopPrice", stop_leg),
+        order.stop_price.to_string(),
+    );
+
+    if let Some(stop_limit_price) = order.stop_limit_price {
+        order_parameters.insert(format!("{}Type", stop_leg), OrderType::StopLossLimit.into());
+        order_parameters.insert(format!("{}Price", stop_leg), stop_limit_price.to_string());
+        order_parameters.insert(format!("{}TimeInForce", stop_leg), TimeInForce::GTC.into());
+    } else {
+        order_parameters.insert(format!("{}Type", stop_leg), OrderType::StopLoss.into());
     }
+
+    order_parameters
 }
@@ -9,6 +9,12 @@ use crate::futures::general::*;
 use crate::futures::market::*;
 use crate::userstream::*;
 use crate::client::*;
+use crate::signer::*;
+use crate::rate_limit::Cost;
+use reqwest::Method;
+use std::sync::Arc;
+#[cfg(feature = "blocking")]
+use crate::blocking;
 
 #[allow(clippy::all)]
 pub enum API {
@@ -37,6 +43,7 @@ pub enum Spot {
     OpenOrders,
     AllOrders,
     Oco,
+    CancelReplace,
     OrderList,
     AllOrderList,
     OpenOrderList,
@@ -102,7 +109,8 @@ impl From<API> for String {
                 Spot::OrderTest => "/api/v3/order/test",
                 Spot::OpenOrders => "/api/v3/openOrders",
                 Spot::AllOrders => "/api/v3/allOrders",
-                Spot::Oco => "/api/v3/order/oco",
+                Spot::Oco => "/api/v3/orderList/oco",
+                Spot::CancelReplace => "/api/v3/order/cancelReplace",
                 Spot::OrderList => "/api/v3/orderList",
                 Spot::AllOrderList => "/api/v3/allOrderList",
                 Spot::OpenOrderList => "/api/v3/openOrderList",
@@ -150,10 +158,130 @@ impl From<API> for String {
     }
 }
 
-pub trait Binance {
+impl API {
+    // Request weight and order count, as documented per endpoint. Where the weight
+    // depends on the parameters it follows `limit` and whether `symbol` is set.
+    pub(crate) fn cost(&self, method: &Method, request: Option<&str>) -> Cost {
+        Cost {
+            weight: self.weight(method, request),
+            orders: self.orders(method, request),
+        }
+    }
+
+    // Only requests creating orders count against the ORDERS limits, so querying or
+    // cancelling orders still works at the limit
+    fn orders(&self, method: &Method, request: Option<&str>) -> u64 {
+        if *method != Method::POST {
+            return 0;
+        }
+        match self {
+            API::Spot(Spot::Order) | API::Spot(Spot::CancelReplace) => 1,
+            // Both orders of the list count
+            API::Spot(Spot::Oco) => 2,
+            API::Futures(Futures::Order) => 1,
+            API::Futures(Futures::BatchOrders) => param(request, "batchOrders")
+                .map_or(1, |orders| {
+                    (orders.matches('{').count() + orders.matches("%7B").count()) as u64
+                }),
+            _ => 0,
+        }
+    }
+
+    fn weight(&self, method: &Method, request: Option<&str>) -> u64 {
+        let symbol = param(request, "symbol").is_some();
+        let limit = param(request, "limit").and_then(|limit| limit.parse::<u64>().ok());
+
+        match self {
+            API::Spot(route) => match route {
+                Spot::Ping | Spot::Time => 1,
+                Spot::ExchangeInfo => 20,
+                Spot::Depth => match limit.unwrap_or(100) {
+                    0..=100 => 5,
+                    101..=500 => 25,
+                    501..=1000 => 50,
+                    _ => 250,
+                },
+                Spot::Trades | Spot::HistoricalTrades => 25,
+                Spot::AggTrades | Spot::Klines | Spot::AvgPrice => 2,
+                Spot::Ticker24hr if symbol => 2,
+                Spot::Ticker24hr => 80,
+                Spot::Price | Spot::BookTicker if symbol => 2,
+                Spot::Price | Spot::BookTicker => 4,
+                Spot::Order | Spot::OrderList if *method == Method::GET => 4,
+                Spot::Order | Spot::OrderList => 1,
+                Spot::OrderTest | Spot::Oco | Spot::CancelReplace => 1,
+                Spot::OpenOrders if *method == Method::DELETE => 1,
+                Spot::OpenOrders if symbol => 6,
+                Spot::OpenOrders => 80,
+                Spot::AllOrders | Spot::AllOrderList | Spot::Account | Spot::MyTrades => 20,
+                Spot::OpenOrderList => 6,
+                Spot::UserDataStream => 2,
+            },
+            API::Futures(route) => match route {
+                Futures::Depth => match limit.unwrap_or(500) {
+                    0..=50 => 2,
+                    51..=100 => 5,
+                    101..=500 => 10,
+                    _ => 20,
+                },
+                Futures::Klines
+                | Futures::ContinuousKlines
+                | Futures::IndexPriceKlines
+                | Futures::MarkPriceKlines => match limit.unwrap_or(500) {
+                    0..=99 => 1,
+                    100..=499 => 2,
+                    500..=1000 => 5,
+                    _ => 10,
+                },
+                Futures::Trades => 5,
+                Futures::HistoricalTrades | Futures::AggTrades => 20,
+                Futures::Ticker24hr if symbol => 1,
+                Futures::Ticker24hr => 40,
+                Futures::TickerPrice if symbol => 1,
+                Futures::TickerPrice => 2,
+                Futures::BookTicker if symbol => 2,
+                Futures::BookTicker => 5,
+                Futures::AllForceOrders if symbol => 20,
+                Futures::AllForceOrders => 50,
+                Futures::Order if *method == Method::POST => 0,
+                Futures::OpenOrders if *method == Method::GET && !symbol => 40,
+                Futures::BatchOrders if *method == Method::POST => 5,
+                Futures::Balance | Futures::PositionsInformation => 5,
+                _ => 1,
+            },
+        }
+    }
+}
+
+// Value of `name` in a query string
+fn param<'a>(request: Option<&'a str>, name: &str) -> Option<&'a str> {
+    request?.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        if key == name {
+            Some(value)
+        } else {
+            None
+        }
+    })
+}
+
+pub trait Binance: Sized {
     fn new(api_key: Option<String>, secret_key: Option<String>) -> Self;
+
+    /// Sign requests with HMAC-SHA256 and the API secret key.
     fn new_with_config(
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> Self {
+        Self::new_with_signer(
+            api_key,
+            HmacSigner::new(secret_key.unwrap_or_default()),
+            config,
+        )
+    }
+
+    /// Sign requests with any `Signer`, e.g. an `Ed25519Signer` or `RsaSigner`.
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
     ) -> Self;
 }
 
@@ -162,11 +290,16 @@ impl Binance for General {
         Self::new_with_config(api_key, secret_key, &Config::default())
     }
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
     ) -> General {
         General {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new(
+                api_key,
+                Arc::new(signer),
+                config.rest_api_endpoint.clone(),
+                config,
+            ),
         }
     }
 }
@@ -176,11 +309,16 @@ impl Binance for Account {
         Self::new_with_config(api_key, secret_key, &Config::default())
     }
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
     ) -> Account {
         Account {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new(
+                api_key,
+                Arc::new(signer),
+                config.rest_api_endpoint.clone(),
+                config,
+            ),
             recv_window: config.recv_window,
         }
     }
@@ -191,11 +329,16 @@ impl Binance for Market {
         Self::new_with_config(api_key, secret_key, &Config::default())
     }
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
     ) -> Market {
         Market {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new(
+                api_key,
+                Arc::new(signer),
+                config.rest_api_endpoint.clone(),
+                config,
+            ),
             recv_window: config.recv_window,
         }
     }
@@ -206,11 +349,16 @@ impl Binance for UserStream {
         Self::new_with_config(api_key, secret_key, &Config::default())
     }
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
     ) -> UserStream {
         UserStream {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new(
+                api_key,
+                Arc::new(signer),
+                config.rest_api_endpoint.clone(),
+                config,
+            ),
             recv_window: config.recv_window,
         }
     }
@@ -225,14 +373,15 @@ impl Binance for FuturesGeneral {
         Self::new_with_config(api_key, secret_key, &Config::default())
     }
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
     ) -> FuturesGeneral {
         FuturesGeneral {
             client: Client::new(
                 api_key,
-                secret_key,
+                Arc::new(signer),
                 config.futures_rest_api_endpoint.clone(),
+                config,
             ),
         }
     }
@@ -243,14 +392,15 @@ impl Binance for FuturesMarket {
         Self::new_with_config(api_key, secret_key, &Config::default())
     }
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
     ) -> FuturesMarket {
         FuturesMarket {
             client: Client::new(
                 api_key,
-                secret_key,
+                Arc::new(signer),
                 config.futures_rest_api_endpoint.clone(),
+                config,
             ),
             recv_window: config.recv_window,
         }
@@ -262,14 +412,15 @@ impl Binance for FuturesAccount {
         Self::new_with_config(api_key, secret_key, &Config::default())
     }
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
     ) -> FuturesAccount {
         FuturesAccount {
             client: Client::new(
                 api_key,
-                secret_key,
+                Arc::new(signer),
                 config.futures_rest_api_endpoint.clone(),
+                config,
             ),
             recv_window: config.recv_window,
         }
@@ -281,14 +432,193 @@ impl Binance for FuturesUserStream {
         Self::new_with_config(api_key, secret_key, &Config::default())
     }
 
-    fn new_with_config(
-        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
     ) -> FuturesUserStream {
         FuturesUserStream {
             client: Client::new(
                 api_key,
-                secret_key,
+                Arc::new(signer),
+                config.futures_rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+// *****************************************************
+//              Blocking API
+// *****************************************************
+
+#[cfg(feature = "blocking")]
+impl Binance for blocking::General {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> blocking::General {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
+    ) -> blocking::General {
+        blocking::General {
+            client: blocking::client::Client::new(
+                api_key,
+                Arc::new(signer),
+                config.rest_api_endpoint.clone(),
+                config,
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Binance for blocking::Account {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> blocking::Account {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
+    ) -> blocking::Account {
+        blocking::Account {
+            client: blocking::client::Client::new(
+                api_key,
+                Arc::new(signer),
+                config.rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Binance for blocking::Market {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> blocking::Market {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
+    ) -> blocking::Market {
+        blocking::Market {
+            client: blocking::client::Client::new(
+                api_key,
+                Arc::new(signer),
+                config.rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Binance for blocking::UserStream {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> blocking::UserStream {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
+    ) -> blocking::UserStream {
+        blocking::UserStream {
+            client: blocking::client::Client::new(
+                api_key,
+                Arc::new(signer),
+                config.rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Binance for blocking::futures::FuturesGeneral {
+    fn new(
+        api_key: Option<String>, secret_key: Option<String>,
+    ) -> blocking::futures::FuturesGeneral {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
+    ) -> blocking::futures::FuturesGeneral {
+        blocking::futures::FuturesGeneral {
+            client: blocking::client::Client::new(
+                api_key,
+                Arc::new(signer),
+                config.futures_rest_api_endpoint.clone(),
+                config,
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Binance for blocking::futures::FuturesMarket {
+    fn new(
+        api_key: Option<String>, secret_key: Option<String>,
+    ) -> blocking::futures::FuturesMarket {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
+    ) -> blocking::futures::FuturesMarket {
+        blocking::futures::FuturesMarket {
+            client: blocking::client::Client::new(
+                api_key,
+                Arc::new(signer),
+                config.futures_rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Binance for blocking::futures::FuturesAccount {
+    fn new(
+        api_key: Option<String>, secret_key: Option<String>,
+    ) -> blocking::futures::FuturesAccount {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
+    ) -> blocking::futures::FuturesAccount {
+        blocking::futures::FuturesAccount {
+            client: blocking::client::Client::new(
+                api_key,
+                Arc::new(signer),
+                config.futures_rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Binance for blocking::futures::FuturesUserStream {
+    fn new(
+        api_key: Option<String>, secret_key: Option<String>,
+    ) -> blocking::futures::FuturesUserStream {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_signer<S: Signer + 'static>(
+        api_key: Option<String>, signer: S, config: &Config,
+    ) -> blocking::futures::FuturesUserStream {
+        blocking::futures::FuturesUserStream {
+            client: blocking::client::Client::new(
+                api_key,
+                Arc::new(signer),
                 config.futures_rest_api_endpoint.clone(),
+                config,
             ),
             recv_window: config.recv_window,
         }
@@ -0,0 +1,769 @@
+use crate::util::*;
+use crate::model::*;
+use crate::blocking::client::*;
+use crate::errors::*;
+use std::collections::BTreeMap;
+use crate::api::API;
+use crate::api::Spot;
+use crate::account::{
+    build_oco_order, build_order, build_quote_quantity_order, cancel_replace_result, OcoRequest,
+    OrderQuantityRequest, OrderQuoteQuantityRequest,
+};
+
+pub use crate::account::{
+    CancelReplaceMode, OrderRequest, OrderResponseType, OrderSide, OrderType,
+    SelfTradePreventionMode, TimeInForce,
+};
+
+#[derive(Clone)]
+pub struct Account {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl Account {
+    // Account Information
+    pub fn get_account(&self) -> Result<AccountInformation> {
+        let request = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::Account), Some(request))
+    }
+
+    // Balance for ONE Asset
+    pub fn get_balance<S>(&self, asset: S) -> Result<Balance>
+    where
+        S: Into<String>,
+    {
+        match self.get_account() {
+            Ok(account) => {
+                let cmp_asset = asset.into();
+                for balance in account.balances {
+                    if balance.asset == cmp_asset {
+                        return Ok(balance);
+                    }
+                }
+                bail!("Asset not found");
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // Current open orders for ONE symbol
+    pub fn get_open_orders<S>(&self, symbol: S) -> Result<Vec<Order>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::OpenOrders), Some(request))
+    }
+
+    // All current open orders
+    pub fn get_all_open_orders(&self) -> Result<Vec<Order>> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::OpenOrders), Some(request))
+    }
+
+    // All orders for ONE symbol: active, canceled or filled
+    //
+    // From `order_id` onwards when set, otherwise the most recent ones
+    pub fn get_all_orders<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, order_id: S2, start_time: S3, end_time: S4, limit: S5,
+    ) -> Result<Vec<Order>>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        if let Some(order_id) = order_id.into() {
+            parameters.insert("orderId".into(), order_id.to_string());
+        }
+        if let Some(start_time) = start_time.into() {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = end_time.into() {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(limit) = limit.into() {
+            parameters.insert("limit".into(), limit.to_string());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::AllOrders), Some(request))
+    }
+
+    // Cancel all open orders for ONE symbol
+    pub fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<Vec<OrderCanceled>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Spot(Spot::OpenOrders), Some(request))
+    }
+
+    // Check an order's status
+    pub fn order_status<S>(&self, symbol: S, order_id: u64) -> Result<Order>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::Order), Some(request))
+    }
+
+    // Check an order's status by the client order id it was placed with
+    pub fn order_status_with_client_id<S1, S2>(
+        &self, symbol: S1, orig_client_order_id: S2,
+    ) -> Result<Order>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("origClientOrderId".into(), orig_client_order_id.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::Order), Some(request))
+    }
+
+    /// Place a test status order
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_order_status<S>(&self, symbol: S, order_id: u64) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed::<()>(API::Spot(Spot::OrderTest), Some(request))
+    }
+
+    // Place a LIMIT order - BUY
+    pub fn limit_buy<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let buy: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price: None,
+            order_side: OrderSide::Buy,
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_order(buy);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Order), request)
+    }
+
+    /// Place a test limit order - BUY
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_limit_buy<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<()>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let buy: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price: None,
+            order_side: OrderSide::Buy,
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_order(buy);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+    }
+
+    // Place a LIMIT order - SELL
+    pub fn limit_sell<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price: None,
+            order_side: OrderSide::Sell,
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Order), request)
+    }
+
+    /// Place a test LIMIT order - SELL
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_limit_sell<S, F>(&self, symbol: S, qty: F, price: Amount) -> Result<()>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price: None,
+            order_side: OrderSide::Sell,
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+    }
+
+    // Place a MARKET order - BUY
+    pub fn market_buy<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let buy: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price: ZERO,
+            stop_price: None,
+            order_side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_order(buy);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Order), request)
+    }
+
+    /// Place a test MARKET order - BUY
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_market_buy<S, F>(&self, symbol: S, qty: F) -> Result<()>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let buy: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price: ZERO,
+            stop_price: None,
+            order_side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_order(buy);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+    }
+
+    // Place a MARKET order with quote quantity - BUY
+    pub fn market_buy_using_quote_quantity<S, F>(
+        &self, symbol: S, quote_order_qty: F,
+    ) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
+            symbol: symbol.into(),
+            quote_order_qty: quote_order_qty.into(),
+            price: ZERO,
+            order_side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_quote_quantity_order(buy);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Order), request)
+    }
+
+    /// Place a test MARKET order with quote quantity - BUY
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_market_buy_using_quote_quantity<S, F>(
+        &self, symbol: S, quote_order_qty: F,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
+            symbol: symbol.into(),
+            quote_order_qty: quote_order_qty.into(),
+            price: ZERO,
+            order_side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_quote_quantity_order(buy);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+    }
+
+    // Place a MARKET order - SELL
+    pub fn market_sell<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price: ZERO,
+            stop_price: None,
+            order_side: OrderSide::Sell,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Order), request)
+    }
+
+    /// Place a test MARKET order - SELL
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_market_sell<S, F>(&self, symbol: S, qty: F) -> Result<()>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price: ZERO,
+            stop_price: None,
+            order_side: OrderSide::Sell,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+    }
+
+    // Place a MARKET order with quote quantity - SELL
+    pub fn market_sell_using_quote_quantity<S, F>(
+        &self, symbol: S, quote_order_qty: F,
+    ) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
+            symbol: symbol.into(),
+            quote_order_qty: quote_order_qty.into(),
+            price: ZERO,
+            order_side: OrderSide::Sell,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_quote_quantity_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Order), request)
+    }
+
+    /// Place a test MARKET order with quote quantity - SELL
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_market_sell_using_quote_quantity<S, F>(
+        &self, symbol: S, quote_order_qty: F,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
+            symbol: symbol.into(),
+            quote_order_qty: quote_order_qty.into(),
+            price: ZERO,
+            order_side: OrderSide::Sell,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::GTC,
+        };
+        let order = build_quote_quantity_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+    }
+
+    /// Place a stop limit buy order
+    pub fn stop_limit_buy_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, time_in_force: TimeInForce,
+    ) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price: Some(stop_price),
+            order_side: OrderSide::Buy,
+            order_type: OrderType::StopLossLimit,
+            time_in_force,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Order), request)
+    }
+
+    /// Place a test stop limit buy order
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_stop_limit_buy_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, time_in_force: TimeInForce,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price: Some(stop_price),
+            order_side: OrderSide::Buy,
+            order_type: OrderType::StopLossLimit,
+            time_in_force,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+    }
+
+    /// Place a custom order
+    #[allow(clippy::too_many_arguments)]
+    pub fn custom_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Option<Amount>, order_side: OrderSide,
+        order_type: OrderType, time_in_force: TimeInForce,
+    ) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price,
+            order_side,
+            order_type,
+            time_in_force,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Order), request)
+    }
+
+    /// Place a test custom order
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_custom_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, order_side: OrderSide, order_type: OrderType,
+        time_in_force: TimeInForce,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderQuantityRequest = OrderQuantityRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price: None,
+            order_side,
+            order_type,
+            time_in_force,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<()>(API::Spot(Spot::OrderTest), request)
+    }
+
+    /// Place any spot order built with `OrderRequest`
+    ///
+    /// The request is validated first, so missing parameters fail without a round trip.
+    pub fn place_order(&self, order: OrderRequest) -> Result<OrderResponse> {
+        let order = order.build()?;
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Order), request)
+    }
+
+    /// Place a test order built with `OrderRequest`
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_order(&self, order: OrderRequest) -> Result<()> {
+        let order = order.build()?;
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed::<TestResponse>(API::Spot(Spot::OrderTest), request)?;
+
+        Ok(())
+    }
+
+    // Check an order's status
+    pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<OrderCanceled>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Spot(Spot::Order), Some(request))
+    }
+
+    // Cancel an order by the client order id it was placed with
+    //
+    // `new_client_order_id` identifies the cancellation itself
+    pub fn cancel_order_with_client_id<S1, S2>(
+        &self, symbol: S1, orig_client_order_id: S2, new_client_order_id: Option<&str>,
+    ) -> Result<OrderCanceled>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("origClientOrderId".into(), orig_client_order_id.into());
+
+        if let Some(new_client_order_id) = new_client_order_id {
+            parameters.insert("newClientOrderId".into(), new_client_order_id.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Spot(Spot::Order), Some(request))
+    }
+
+    /// Cancel an existing order and place a new one in a single request
+    ///
+    /// The new order is placed on the symbol of `order`. When either step fails, the
+    /// returned `CancelReplaceResponse` holds the error of that step instead of
+    /// `Err`, so check `cancel_result` and `new_order_result`.
+    pub fn cancel_replace_order(
+        &self, cancel_order_id: u64, order: OrderRequest, mode: CancelReplaceMode,
+    ) -> Result<CancelReplaceResponse> {
+        let mut parameters = order.build()?;
+        parameters.insert("cancelOrderId".into(), cancel_order_id.to_string());
+        parameters.insert("cancelReplaceMode".into(), mode.into());
+
+        self.cancel_replace(parameters)
+    }
+
+    /// Cancel an existing order by client order id and place a new one in a single request
+    pub fn cancel_replace_order_with_client_id<S>(
+        &self, cancel_orig_client_order_id: S, order: OrderRequest, mode: CancelReplaceMode,
+    ) -> Result<CancelReplaceResponse>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = order.build()?;
+        parameters.insert(
+            "cancelOrigClientOrderId".into(),
+            cancel_orig_client_order_id.into(),
+        );
+        parameters.insert("cancelReplaceMode".into(), mode.into());
+
+        self.cancel_replace(parameters)
+    }
+
+    fn cancel_replace(
+        &self, parameters: BTreeMap<String, String>,
+    ) -> Result<CancelReplaceResponse> {
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let result = self
+            .client
+            .post_signed(API::Spot(Spot::CancelReplace), request);
+
+        cancel_replace_result(result)
+    }
+
+    /// Place a test cancel order
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed::<()>(API::Spot(Spot::OrderTest), Some(request))
+    }
+
+    /// Place an OCO order - BUY
+    ///
+    /// A LIMIT_MAKER at `price` below the market and a stop at `stop_price` above it.
+    /// With `stop_limit_price` the stop is a STOP_LOSS_LIMIT order (GTC), otherwise a
+    /// STOP_LOSS market order.
+    pub fn oco_buy<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount,
+        stop_limit_price: Option<Amount>,
+    ) -> Result<OrderList>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let buy = OcoRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price,
+            stop_limit_price,
+            order_side: OrderSide::Buy,
+        };
+        let order = build_oco_order(buy);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Oco), request)
+    }
+
+    /// Place an OCO order - SELL
+    ///
+    /// A LIMIT_MAKER take-profit at `price` and a stop-loss at `stop_price`.
+    /// With `stop_limit_price` the stop is a STOP_LOSS_LIMIT order (GTC), otherwise a
+    /// STOP_LOSS market order.
+    pub fn oco_sell<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount,
+        stop_limit_price: Option<Amount>,
+    ) -> Result<OrderList>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell = OcoRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price,
+            stop_limit_price,
+            order_side: OrderSide::Sell,
+        };
+        let order = build_oco_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Oco), request)
+    }
+
+    // Cancel all orders of an order list
+    pub fn cancel_order_list<S>(&self, symbol: S, order_list_id: u64) -> Result<OrderList>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderListId".into(), order_list_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Spot(Spot::OrderList), Some(request))
+    }
+
+    // Check an order list's status
+    pub fn order_list_status(&self, order_list_id: u64) -> Result<OrderList> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("orderListId".into(), order_list_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::OrderList), Some(request))
+    }
+
+    // Order lists from `from_id` on or within the given times, up to `limit` (500 by
+    // default) of them
+    pub fn get_all_order_lists<S1, S2, S3, S4>(
+        &self, from_id: S1, start_time: S2, end_time: S3, limit: S4,
+    ) -> Result<Vec<OrderList>>
+    where
+        S1: Into<Option<u64>>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(from_id) = from_id.into() {
+            parameters.insert("fromId".into(), from_id.to_string());
+        }
+        if let Some(start_time) = start_time.into() {
+            parameters.insert("startTime".into(), start_time.to_string());
+        }
+        if let Some(end_time) = end_time.into() {
+            parameters.insert("endTime".into(), end_time.to_string());
+        }
+        if let Some(limit) = limit.into() {
+            parameters.insert("limit".into(), limit.to_string());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::AllOrderList), Some(request))
+    }
+
+    // All open order lists
+    pub fn get_open_order_lists(&self) -> Result<Vec<OrderList>> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::OpenOrderList), Some(request))
+    }
+
+    // Trade history
+    pub fn trade_history<S>(&self, symbol: S) -> Result<Vec<TradeHistory>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::MyTrades), Some(request))
+    }
+}
@@ -0,0 +1,289 @@
+use crate::errors::*;
+use reqwest::{Method, StatusCode};
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT, CONTENT_TYPE};
+use serde::de::DeserializeOwned;
+use crate::api::{API, Spot, Futures};
+use crate::client::{has_client_order_id, is_retryable_status, response_error, retry_after};
+use crate::config::{Config, RateLimiter, RetryPolicy};
+use crate::rate_limit::{Cost, RateLimits};
+use crate::time_sync::TimeSync;
+use crate::signer::Signer;
+use crate::model::ServerTime;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+#[derive(Clone)]
+pub struct Client {
+    api_key: String,
+    signer: Arc<dyn Signer>,
+    host: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
+    rate_limits: RateLimits,
+    time_sync: TimeSync,
+    time_sync_interval: Option<Duration>,
+    inner_client: reqwest::blocking::Client,
+}
+
+impl Client {
+    pub fn new(
+        api_key: Option<String>, signer: Arc<dyn Signer>, host: String, config: &Config,
+    ) -> Self {
+        Client {
+            api_key: api_key.unwrap_or_else(|| "".into()),
+            signer,
+            host,
+            retry_policy: config.retry_policy.clone(),
+            rate_limiter: config.rate_limiter.clone(),
+            rate_limits: config.rate_limits.clone().unwrap_or_default(),
+            time_sync: TimeSync::new(),
+            time_sync_interval: config.time_sync_interval,
+            inner_client: reqwest::blocking::Client::builder()
+                .pool_idle_timeout(None)
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// Usage reported by Binance in the response headers of this client's requests.
+    pub fn rate_limits(&self) -> &RateLimits {
+        &self.rate_limits
+    }
+
+    /// Server time offset applied to the `timestamp` of signed requests.
+    pub fn time_sync(&self) -> &TimeSync {
+        &self.time_sync
+    }
+
+    // Paginated reads send many requests in a row, so they wait for the request
+    // weight to free up even when the configured limiter does not
+    pub(crate) fn wait_for_weight(&self, endpoint: &API, request: &str) -> Result<()> {
+        let cost = endpoint.cost(&Method::GET, Some(request));
+        loop {
+            let wait = self.rate_limits.wait_time(cost);
+            if wait == Duration::default() {
+                return Ok(());
+            }
+            std::thread::sleep(wait);
+        }
+    }
+
+    pub fn get_signed<T: DeserializeOwned>(
+        &self, endpoint: API, request: Option<String>,
+    ) -> Result<T> {
+        self.signed(Method::GET, endpoint, request, true)
+    }
+
+    pub fn post_signed<T: DeserializeOwned>(&self, endpoint: API, request: String) -> Result<T> {
+        let retry = self.retry_policy.retry_orders && has_client_order_id(&request);
+
+        self.signed(Method::POST, endpoint, Some(request), retry)
+    }
+
+    pub fn delete_signed<T: DeserializeOwned>(
+        &self, endpoint: API, request: Option<String>,
+    ) -> Result<T> {
+        self.signed(Method::DELETE, endpoint, request, false)
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, endpoint: API, request: Option<String>) -> Result<T> {
+        let cost = endpoint.cost(&Method::GET, request.as_deref());
+        let mut url: String = format!("{}{}", self.host, String::from(endpoint));
+        if let Some(request) = request {
+            if !request.is_empty() {
+                url.push_str(format!("?{}", request).as_str());
+            }
+        }
+
+        let client = &self.inner_client;
+        let builder = client.get(url.as_str());
+
+        self.execute(builder, true, cost)
+    }
+
+    pub fn post<T: DeserializeOwned>(&self, endpoint: API) -> Result<T> {
+        let cost = endpoint.cost(&Method::POST, None);
+        let url: String = format!("{}{}", self.host, String::from(endpoint));
+
+        let client = &self.inner_client;
+        let builder = client
+            .post(url.as_str())
+            .headers(self.build_headers(false)?);
+
+        self.execute(builder, false, cost)
+    }
+
+    pub fn put<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
+        let cost = endpoint.cost(&Method::PUT, None);
+        let url: String = format!("{}{}", self.host, String::from(endpoint));
+        let data: String = format!("listenKey={}", listen_key);
+
+        let client = &self.inner_client;
+        let builder = client
+            .put(url.as_str())
+            .headers(self.build_headers(false)?)
+            .body(data);
+
+        self.execute(builder, true, cost)
+    }
+
+    pub fn delete<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
+        let cost = endpoint.cost(&Method::DELETE, None);
+        let url: String = format!("{}{}", self.host, String::from(endpoint));
+        let data: String = format!("listenKey={}", listen_key);
+
+        let client = &self.inner_client;
+        let builder = client
+            .delete(url.as_str())
+            .headers(self.build_headers(false)?)
+            .body(data);
+
+        self.execute(builder, false, cost)
+    }
+
+    // Stamp, sign and send, re-syncing the server time once when Binance rejects the
+    // timestamp with -1021. The request was not processed then, so resending is safe.
+    fn signed<T: DeserializeOwned>(
+        &self, method: Method, endpoint: API, request: Option<String>, retry: bool,
+    ) -> Result<T> {
+        let spot = matches!(endpoint, API::Spot(_));
+        if self.time_sync.is_stale(self.time_sync_interval) {
+            // A failed sync leaves the previous offset in place
+            let _ = self.sync_time(spot);
+        }
+
+        let cost = endpoint.cost(&method, request.as_deref());
+        let path = String::from(endpoint);
+        let mut resynced = false;
+
+        loop {
+            let request = match &request {
+                Some(request) => Some(self.time_sync.stamp(request)?),
+                None => None,
+            };
+            let url = self.sign_request(&path, request)?;
+            let builder = self
+                .inner_client
+                .request(method.clone(), url.as_str())
+                .headers(self.build_headers(true)?);
+
+            match self.execute(builder, retry, cost) {
+                Err(Error(ErrorKind::BinanceError(ref error), _))
+                    if error.error_code() == BinanceErrorCode::InvalidTimestamp && !resynced => {}
+                result => return result,
+            }
+
+            // Should the sync fail, the resend returns the second -1021
+            resynced = true;
+            let _ = self.sync_time(spot);
+        }
+    }
+
+    fn sync_time(&self, spot: bool) -> Result<()> {
+        let endpoint = if spot {
+            API::Spot(Spot::Time)
+        } else {
+            API::Futures(Futures::Time)
+        };
+        let cost = endpoint.cost(&Method::GET, None);
+        let url: String = format!("{}{}", self.host, String::from(endpoint));
+        let builder = self.inner_client.get(url.as_str());
+
+        // Not retried, so a server that can't be reached doesn't hold up every
+        // signed request
+        let sent = SystemTime::now();
+        let server_time: ServerTime = self.execute(builder, false, cost)?;
+        self.time_sync
+            .update(sent, SystemTime::now(), server_time.server_time);
+
+        Ok(())
+    }
+
+    // Request must be signed
+    fn sign_request(&self, path: &str, request: Option<String>) -> Result<String> {
+        let request = request.unwrap_or_default();
+        let signature = self.signer.sign(&request)?;
+
+        Ok(format!(
+            "{}{}?{}&signature={}",
+            self.host, path, request, signature
+        ))
+    }
+
+    fn build_headers(&self, content_type: bool) -> Result<HeaderMap> {
+        let mut custom_headers = HeaderMap::new();
+
+        custom_headers.insert(USER_AGENT, HeaderValue::from_static("binance-rs"));
+        if content_type {
+            custom_headers.insert(
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/x-www-form-urlencoded"),
+            );
+        }
+        custom_headers.insert(
+            HeaderName::from_static("x-mbx-apikey"),
+            HeaderValue::from_str(self.api_key.as_str())?,
+        );
+
+        Ok(custom_headers)
+    }
+
+    // Send the request, retrying on 5xx, 429 and connection errors when `retry` is set.
+    // `cost` is reserved against the rate limits before each attempt.
+    fn execute<T: DeserializeOwned>(
+        &self, builder: RequestBuilder, retry: bool, cost: Cost,
+    ) -> Result<T> {
+        let max_attempts = if retry {
+            self.retry_policy.max_attempts
+        } else {
+            1
+        };
+        let mut attempt = 1;
+
+        loop {
+            // Another request may take the freed capacity first, so check again
+            while let Some(wait) = self.rate_limits.acquire(&self.rate_limiter, cost)? {
+                std::thread::sleep(wait);
+            }
+
+            let pending = match builder.try_clone() {
+                Some(pending) if attempt < max_attempts => pending,
+                _ => {
+                    let response = builder.send()?;
+                    self.rate_limits
+                        .update(response.status(), response.headers());
+                    return self.handler(response);
+                }
+            };
+
+            let retry_after = match pending.send() {
+                Ok(response) => {
+                    self.rate_limits
+                        .update(response.status(), response.headers());
+                    if !is_retryable_status(response.status()) {
+                        return self.handler(response);
+                    }
+                    retry_after(response.headers())
+                }
+                Err(e) if e.is_connect() || e.is_timeout() => None,
+                Err(e) => return Err(e.into()),
+            };
+
+            std::thread::sleep(self.retry_policy.backoff(attempt, retry_after));
+            attempt += 1;
+        }
+    }
+
+    fn handler<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
+        match response.status() {
+            StatusCode::OK => Ok(response.json::<T>()?),
+            status => {
+                let retry_after = retry_after(response.headers());
+                let body = response.text()?;
+
+                Err(response_error(status, retry_after, &body))
+            }
+        }
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::BTreeMap;
+
+use crate::futures::model::*;
+use crate::model::ZERO;
+use crate::util::*;
+use crate::blocking::client::*;
+use crate::errors::*;
+use crate::api::API;
+use crate::api::Futures;
+use crate::futures::account::{build_order, OrderRequest};
+
+pub use crate::futures::account::{MarginType, OrderSide, OrderType, TimeInForce};
+
+#[derive(Clone)]
+pub struct FuturesAccount {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl FuturesAccount {
+    // Set leverage
+    pub fn set_leverage<S, I>(&self, symbol: S, leverage: I) -> Result<Leverage>
+    where
+        S: Into<String>,
+        I: Into<u64>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("leverage".into(), leverage.into().to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Leverage), request)
+    }
+
+    // Set margin type
+    pub fn set_margin_type<S>(&self, symbol: S, margin_type: MarginType) -> Result<Response>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("marginType".into(), margin_type.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::MarginType), request)
+    }
+
+    // All current open orders
+    pub fn get_all_open_orders(&self, symbol: Option<String>) -> Result<Vec<Order>> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(symbol) = symbol {
+            parameters.insert("symbol".into(), symbol);
+        }
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::OpenOrders), Some(request))
+    }
+
+    // Cancel all open orders
+    pub fn cancel_all_open_orders(&self, symbol: Option<String>) -> Result<Response> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(symbol) = symbol {
+            parameters.insert("symbol".into(), symbol);
+        }
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Futures(Futures::CancelAllOpenOrders), Some(request))
+    }
+
+    // Get Balance
+    pub fn get_balance(&self) -> Result<Vec<AccountBalance>> {
+        let parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::Balance), Some(request))
+    }
+
+    // Get Positions
+    pub fn get_positions(&self, symbol: Option<String>) -> Result<Vec<Position>> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(symbol) = symbol {
+            parameters.insert("symbol".into(), symbol);
+        }
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::PositionsInformation), Some(request))
+    }
+
+    /// Place a market buy order
+    pub fn market_buy_order<S, F>(
+        &self, symbol: S, qty: F, reduce_only: bool,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: None,
+            stop_price: None,
+            order_side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: Some(reduce_only),
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a market sell order
+    pub fn market_sell_order<S, F>(
+        &self, symbol: S, qty: F, reduce_only: bool,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: None,
+            stop_price: None,
+            order_side: OrderSide::Sell,
+            order_type: OrderType::Market,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: Some(reduce_only),
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a take profit buy order
+    pub fn take_profit_buy_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, reduce_only: bool,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: Some(price),
+            stop_price: Some(stop_price),
+            order_side: OrderSide::Buy,
+            order_type: OrderType::TakeProfit,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: Some(reduce_only),
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a take profit self order
+    pub fn take_profit_sell_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount, reduce_only: bool,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: Some(price),
+            stop_price: Some(stop_price),
+            order_side: OrderSide::Sell,
+            order_type: OrderType::TakeProfit,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: Some(reduce_only),
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a stop buy order
+    pub fn stop_buy_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: Some(price),
+            stop_price: Some(stop_price),
+            order_side: OrderSide::Buy,
+            order_type: OrderType::Stop,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: None,
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a stop self order
+    pub fn stop_sell_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount, stop_price: Amount,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: Some(price),
+            stop_price: Some(stop_price),
+            order_side: OrderSide::Sell,
+            order_type: OrderType::Stop,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: None,
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a stop market buy order
+    pub fn stop_market_buy_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: None,
+            stop_price: Some(price),
+            order_side: OrderSide::Buy,
+            order_type: OrderType::StopMarket,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: None,
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a stop market self order
+    pub fn stop_market_sell_order<S, F>(
+        &self, symbol: S, qty: F, price: Amount,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: None,
+            stop_price: Some(price),
+            order_side: OrderSide::Sell,
+            order_type: OrderType::StopMarket,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: None,
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a trailing stop market buy order
+    pub fn trailing_stop_market_buy_order<S, F>(
+        &self, symbol: S, qty: F, activation_price: Option<Amount>, callback_rate: Amount,
+        reduce_only: bool,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            activation_price,
+            callback_rate: Some(callback_rate),
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: None,
+            stop_price: None,
+            order_side: OrderSide::Buy,
+            order_type: OrderType::TrailingStopMarket,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: Some(reduce_only),
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a trailing stop market self order
+    pub fn trailing_stop_market_sell_order<S, F>(
+        &self, symbol: S, qty: F, activation_price: Option<Amount>, callback_rate: Amount,
+        reduce_only: bool,
+    ) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            activation_price,
+            callback_rate: Some(callback_rate),
+            symbol: symbol.into(),
+            qty: Some(qty.into()),
+            price: None,
+            stop_price: None,
+            order_side: OrderSide::Sell,
+            order_type: OrderType::TrailingStopMarket,
+            time_in_force: None,
+            close_position: false,
+            reduce_only: Some(reduce_only),
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a stop loss long position buy order
+    pub fn stop_loss_long_position_order<S, F>(&self, symbol: S, price: F) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: None,
+            price: None,
+            stop_price: Some(price.into()),
+            order_side: OrderSide::Sell,
+            order_type: OrderType::StopMarket,
+            time_in_force: None,
+            close_position: true,
+            reduce_only: None,
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Place a stop loss short position order
+    pub fn stop_loss_short_position_order<S, F>(&self, symbol: S, price: F) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+        F: Into<Amount>,
+    {
+        let sell: OrderRequest = OrderRequest {
+            symbol: symbol.into(),
+            qty: None,
+            price: None,
+            stop_price: Some(price.into()),
+            order_side: OrderSide::Buy,
+            order_type: OrderType::StopMarket,
+            time_in_force: None,
+            close_position: true,
+            reduce_only: None,
+            activation_price: None,
+            callback_rate: None,
+        };
+        let order = build_order(sell);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::Order), request)
+    }
+
+    /// Cancel an order
+    pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Futures(Futures::Order), Some(request))
+    }
+
+    /// Cancel multiples order
+    pub fn cancel_orders<S>(&self, symbol: S, order_ids: &[u64]) -> Result<Vec<PlacedOrder>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert(
+            "orderIdList".into(),
+            format!("{:?}", order_ids).replace(" ", ""),
+        );
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Futures(Futures::BatchOrders), Some(request))
+    }
+
+    /// Close position
+    pub fn close_position<S>(&self, symbol: S) -> Result<PlacedOrder>
+    where
+        S: Into<String>,
+    {
+        let symbol: String = symbol.into().clone();
+        let positions = self.get_positions(None)?;
+        let position = positions.iter().find(|p| p.symbol == symbol);
+
+        if let Some(position) = position {
+            if position.position_amt > ZERO {
+                self.market_sell_order(symbol, position.position_amt, true)
+            } else {
+                self.market_buy_order(symbol, -position.position_amt, true)
+            }
+        } else {
+            bail!("No positions found for {}", symbol);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+/*!
+## Implemented functionality
+- [x] `Order Book`
+- [x] `Recent Trades List`
+- [ ] `Old Trades Lookup (MARKET_DATA)`
+- [x] `Compressed/Aggregate Trades List`
+- [x] `Kline/Candlestick Data`
+- [x] `Mark Price`
+- [ ] `Get Funding Rate History (MARKET_DATA)`
+- [x] `24hr Ticker Price Change Statistics`
+- [x] `Symbol Price Ticker`
+- [x] `Symbol Order Book Ticker`
+- [x] `Get all Liquidation Orders`
+- [x] `Open Interest`
+- [ ] `Notional and Leverage Brackets (MARKET_DATA)`
+- [ ] `Open Interest Statistics (MARKET_DATA)`
+- [ ] `Top Trader Long/Short Ratio (Accounts) (MARKET_DATA)`
+- [ ] `Top Trader Long/Short Ratio (Positions) (MARKET_DATA)`
+- [ ] `Long/Short Ratio (MARKET_DATA)`
+- [ ] `Taker Buy/Sell Volume (MARKET_DATA)`
+*/
+
+use crate::util::*;
+use crate::futures::model::*;
+use crate::blocking::client::*;
+use crate::errors::*;
+use std::collections::BTreeMap;
+use crate::order_book::{DepthUpdate, LocalOrderBook, UpdateStatus, SNAPSHOT_DEPTH};
+use crate::pagination::{Cursor, Pager, Pages, FUTURES_KLINES_LIMIT, TRADES_LIMIT};
+use crate::api::API;
+use crate::api::Futures;
+
+// TODO
+// Make enums for Strings
+// Add limit parameters to functions
+// Implement all functions
+
+#[derive(Clone)]
+pub struct FuturesMarket {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl FuturesMarket {
+    // Order book (Default 100; max 1000)
+    pub fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        self.client.get(API::Futures(Futures::Depth), Some(request))
+    }
+
+    // Order book at a custom depth. Currently supported values
+    // are 5, 10, 20, 50, 100, 500, 1000
+    pub fn get_custom_depth<S>(&self, symbol: S, depth: u64) -> Result<OrderBook>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("limit".into(), depth.to_string());
+        let request = build_request(&parameters);
+
+        self.client.get(API::Futures(Futures::Depth), Some(request))
+    }
+
+    /// Apply a depth update to `book`, fetching a snapshot when the book needs one:
+    /// before the first update is applied and after a missed update.
+    pub fn update_order_book<U>(&self, book: &mut LocalOrderBook, update: U) -> Result<UpdateStatus>
+    where
+        U: Into<DepthUpdate>,
+    {
+        let status = book.push(update);
+        if book.needs_snapshot() {
+            let snapshot = self.get_custom_depth(book.symbol(), SNAPSHOT_DEPTH)?;
+            book.apply_snapshot(snapshot);
+        }
+
+        Ok(status)
+    }
+
+    pub fn get_trades<S>(&self, symbol: S) -> Result<Trades>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+        self.client
+            .get(API::Futures(Futures::Trades), Some(request))
+    }
+
+    // TODO This may be incomplete, as it hasn't been tested
+    pub fn get_historical_trades<S1, S2, S3>(
+        &self, symbol: S1, from_id: S2, limit: S3,
+    ) -> Result<Trades>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+
+        // Add three optional parameters
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(fi) = from_id.into() {
+            parameters.insert("fromId".into(), format!("{}", fi));
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+
+        self.client
+            .get_signed(API::Futures(Futures::HistoricalTrades), Some(request))
+    }
+
+    pub fn get_agg_trades<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, from_id: S2, start_time: S3, end_time: S4, limit: S5,
+    ) -> Result<AggTrades>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+
+        // Add three optional parameters
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+        if let Some(fi) = from_id.into() {
+            parameters.insert("fromId".into(), format!("{}", fi));
+        }
+
+        let request = build_request(&parameters);
+
+        self.client
+            .get(API::Futures(Futures::AggTrades), Some(request))
+    }
+
+    // Returns up to 'limit' klines for given symbol and interval
+    // https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#klinecandlestick-data
+    pub fn get_klines<S1, S2, S3, S4>(
+        &self, symbol: S1, interval: KlineInterval, limit: S2, start_time: S3, end_time: S4,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u16>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("interval".into(), interval.into());
+
+        // Add three optional parameters
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+
+        let request = build_request(&parameters);
+        let klines: Vec<KlineSummary> = self
+            .client
+            .get(API::Futures(Futures::Klines), Some(request))?;
+
+        Ok(KlineSummaries::AllKlineSummaries(klines))
+    }
+
+    /// All klines opened from `start_time` to `end_time`, or until now without it, as
+    /// an `Iterator` fetching 1500 at a time.
+    pub fn get_klines_range<S1, S2>(
+        &self, symbol: S1, interval: KlineInterval, start_time: u64, end_time: S2,
+    ) -> Pages<'_, KlineSummary>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+    {
+        let symbol: String = symbol.into();
+        let end_time = end_time.into();
+        let pager = Pager::new(
+            Cursor::StartTime(start_time),
+            end_time,
+            FUTURES_KLINES_LIMIT,
+        );
+
+        Pages::new(pager, move |cursor| {
+            let limit = format!("limit={}", FUTURES_KLINES_LIMIT);
+            self.client
+                .wait_for_weight(&API::Futures(Futures::Klines), &limit)?;
+            let KlineSummaries::AllKlineSummaries(klines) = self.get_klines(
+                symbol.clone(),
+                interval,
+                FUTURES_KLINES_LIMIT,
+                cursor.time(),
+                end_time,
+            )?;
+            Ok(klines)
+        })
+    }
+
+    /// All aggregate trades from `start_time` to `end_time`, or until now without it,
+    /// as an `Iterator` fetching 1000 at a time.
+    pub fn get_agg_trades_range<S1, S2>(
+        &self, symbol: S1, start_time: u64, end_time: S2,
+    ) -> Pages<'_, AggTrade>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+    {
+        let symbol: String = symbol.into();
+        let pager = Pager::new(Cursor::StartTime(start_time), end_time.into(), TRADES_LIMIT);
+
+        Pages::new(pager, move |cursor| {
+            let limit = format!("limit={}", TRADES_LIMIT);
+            self.client
+                .wait_for_weight(&API::Futures(Futures::AggTrades), &limit)?;
+            let AggTrades::AllAggTrades(trades) = self.get_agg_trades(
+                symbol.clone(),
+                cursor.id(),
+                cursor.time(),
+                None,
+                TRADES_LIMIT,
+            )?;
+            Ok(trades)
+        })
+    }
+
+    // 24hr ticker price change statistics
+    pub fn get_24h_price_stats<S>(&self, symbol: S) -> Result<PriceStats>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        self.client
+            .get(API::Futures(Futures::Ticker24hr), Some(request))
+    }
+
+    // Latest price for ONE symbol.
+    pub fn get_price<S>(&self, symbol: S) -> Result<SymbolPrice>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+
+        self.client
+            .get(API::Futures(Futures::TickerPrice), Some(request))
+    }
+
+    // Symbols order book ticker
+    // -> Best price/qty on the order book for ALL symbols.
+    pub fn get_all_book_tickers(&self) -> Result<BookTickers> {
+        self.client.get(API::Futures(Futures::BookTicker), None)
+    }
+
+    // -> Best price/qty on the order book for ONE symbol
+    pub fn get_book_ticker<S>(&self, symbol: S) -> Result<Tickers>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+        self.client
+            .get(API::Futures(Futures::BookTicker), Some(request))
+    }
+
+    pub fn get_mark_prices(&self) -> Result<MarkPrices> {
+        self.client.get(API::Futures(Futures::PremiumIndex), None)
+    }
+
+    pub fn get_all_liquidation_orders(&self) -> Result<LiquidationOrders> {
+        self.client.get(API::Futures(Futures::AllForceOrders), None)
+    }
+
+    pub fn open_interest<S>(&self, symbol: S) -> Result<OpenInterest>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(&parameters);
+        self.client
+            .get(API::Futures(Futures::OpenInterest), Some(request))
+    }
+}
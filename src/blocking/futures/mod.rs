@@ -0,0 +1,9 @@
+pub mod account;
+pub mod general;
+pub mod market;
+pub mod userstream;
+
+pub use self::account::FuturesAccount;
+pub use self::general::FuturesGeneral;
+pub use self::market::FuturesMarket;
+pub use self::userstream::FuturesUserStream;
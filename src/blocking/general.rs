@@ -0,0 +1,52 @@
+use crate::model::*;
+use crate::blocking::client::*;
+use crate::errors::*;
+use crate::api::API;
+use crate::api::Spot;
+
+#[derive(Clone)]
+pub struct General {
+    pub client: Client,
+}
+
+impl General {
+    // Test connectivity
+    pub fn ping(&self) -> Result<String> {
+        self.client.get::<serde_json::Value>(API::Spot(Spot::Ping), None)?;
+        Ok("pong".into())
+    }
+
+    // Check server time
+    pub fn get_server_time(&self) -> Result<ServerTime> {
+        self.client.get(API::Spot(Spot::Time), None)
+    }
+
+    // Obtain exchange information
+    // - Current exchange trading rules and symbol information
+    // Also seeds the client's rate limits
+    pub fn exchange_info(&self) -> Result<ExchangeInformation> {
+        let info: ExchangeInformation = self.client.get(API::Spot(Spot::ExchangeInfo), None)?;
+        self.client.rate_limits().seed(&info.rate_limits);
+
+        Ok(info)
+    }
+
+    // Get Symbol information
+    pub fn get_symbol_info<S>(&self, symbol: S) -> Result<Symbol>
+    where
+        S: Into<String>,
+    {
+        let upper_symbol = symbol.into().to_uppercase();
+        match self.exchange_info() {
+            Ok(info) => {
+                for item in info.symbols {
+                    if item.symbol == upper_symbol {
+                        return Ok(item);
+                    }
+                }
+                bail!("Symbol not found")
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Blocking counterparts of the async REST clients.
+//!
+//! Enabled with the `blocking` feature. The types in this module expose the same
+//! endpoints as their async counterparts, so both flavours can be used in the
+//! same build:
+//!
+//! ```ignore
+//! use binance::api::*;
+//!
+//! let market: binance::blocking::Market = Binance::new(None, None);
+//! let price = market.get_price("BNBBTC")?;
+//! ```
+
+// Each endpoint is written out in both flavours: `maybe_async` only builds one of them at
+// a time, and a generating macro would hide the signatures and docs. What goes beyond
+// sending the request (building parameters, decoding failures) lives in the async
+// modules and is shared from there.
+pub(crate) mod client;
+
+pub mod account;
+pub mod futures;
+pub mod general;
+pub mod market;
+pub mod userstream;
+
+pub use self::account::Account;
+pub use self::general::General;
+pub use self::market::Market;
+pub use self::userstream::UserStream;
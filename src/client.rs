@@ -1,44 +1,43 @@
-use hex::encode as hex_encode;
-use hmac::{Hmac, Mac, NewMac};
 use crate::errors::*;
-use reqwest::StatusCode;
-
-#[cfg(feature = "blocking")]
-use reqwest::blocking::Response;
-
-#[cfg(not(feature = "blocking"))]
-use reqwest::Response;
-
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT, CONTENT_TYPE};
-use sha2::Sha256;
+use reqwest::{Method, StatusCode};
+use reqwest::{RequestBuilder, Response};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT, CONTENT_TYPE, RETRY_AFTER};
 use serde::de::DeserializeOwned;
-use crate::api::API;
-
-#[cfg(feature = "blocking")]
-#[derive(Clone)]
-pub struct Client {
-    api_key: String,
-    secret_key: String,
-    host: String,
-    inner_client: reqwest::blocking::Client,
-}
+use crate::api::{API, Spot, Futures};
+use crate::config::{Config, RateLimiter, RetryPolicy};
+use crate::rate_limit::{Cost, RateLimits};
+use crate::time_sync::TimeSync;
+use crate::signer::Signer;
+use crate::model::ServerTime;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
-#[cfg(not(feature = "blocking"))]
 #[derive(Clone)]
 pub struct Client {
     api_key: String,
-    secret_key: String,
+    signer: Arc<dyn Signer>,
     host: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
+    rate_limits: RateLimits,
+    time_sync: TimeSync,
+    time_sync_interval: Option<Duration>,
     inner_client: reqwest::Client,
 }
 
-#[cfg(not(feature = "blocking"))]
 impl Client {
-    pub fn new(api_key: Option<String>, secret_key: Option<String>, host: String) -> Self {
+    pub fn new(
+        api_key: Option<String>, signer: Arc<dyn Signer>, host: String, config: &Config,
+    ) -> Self {
         Client {
             api_key: api_key.unwrap_or_else(|| "".into()),
-            secret_key: secret_key.unwrap_or_else(|| "".into()),
+            signer,
             host,
+            retry_policy: config.retry_policy.clone(),
+            rate_limiter: config.rate_limiter.clone(),
+            rate_limits: config.rate_limits.clone().unwrap_or_default(),
+            time_sync: TimeSync::new(),
+            time_sync_interval: config.time_sync_interval,
             inner_client: reqwest::Client::builder()
                 .pool_idle_timeout(None)
                 .build()
@@ -46,51 +45,54 @@ impl Client {
         }
     }
 
+    /// Usage reported by Binance in the response headers of this client's requests.
+    pub fn rate_limits(&self) -> &RateLimits {
+        &self.rate_limits
+    }
+
+    /// Server time offset applied to the `timestamp` of signed requests.
+    pub fn time_sync(&self) -> &TimeSync {
+        &self.time_sync
+    }
+
+    // Paginated reads send many requests in a row, so they wait for the request
+    // weight to free up even when the configured limiter does not
+    pub(crate) async fn wait_for_weight(&self, endpoint: &API, request: &str) -> Result<()> {
+        let cost = endpoint.cost(&Method::GET, Some(request));
+        loop {
+            let wait = self.rate_limits.wait_time(cost);
+            if wait == Duration::default() {
+                return Ok(());
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
     pub async fn get_signed<T: DeserializeOwned>(
         &self, endpoint: API, request: Option<String>,
     ) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let client = &self.inner_client;
-        let response = client
-            .get(url.as_str())
-            .headers(self.build_headers(true)?)
-            .send()
-            .await?;
-
-        self.handler(response).await
+        self.signed(Method::GET, endpoint, request, true).await
     }
 
     pub async fn post_signed<T: DeserializeOwned>(
         &self, endpoint: API, request: String,
     ) -> Result<T> {
-        let url = self.sign_request(endpoint, Some(request));
-        let client = &self.inner_client;
-        let response = client
-            .post(url.as_str())
-            .headers(self.build_headers(true)?)
-            .send()
-            .await?;
+        let retry = self.retry_policy.retry_orders && has_client_order_id(&request);
 
-        self.handler(response).await
+        self.signed(Method::POST, endpoint, Some(request), retry)
+            .await
     }
 
     pub async fn delete_signed<T: DeserializeOwned>(
         &self, endpoint: API, request: Option<String>,
     ) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let client = &self.inner_client;
-        let response = client
-            .delete(url.as_str())
-            .headers(self.build_headers(true)?)
-            .send()
-            .await?;
-
-        self.handler(response).await
+        self.signed(Method::DELETE, endpoint, request, false).await
     }
 
     pub async fn get<T: DeserializeOwned>(
         &self, endpoint: API, request: Option<String>,
     ) -> Result<T> {
+        let cost = endpoint.cost(&Method::GET, request.as_deref());
         let mut url: String = format!("{}{}", self.host, String::from(endpoint));
         if let Some(request) = request {
             if !request.is_empty() {
@@ -99,239 +101,118 @@ impl Client {
         }
 
         let client = &self.inner_client;
-        let response = client.get(url.as_str()).send().await?;
+        let builder = client.get(url.as_str());
 
-        self.handler(response).await
+        self.execute(builder, true, cost).await
     }
 
     pub async fn post<T: DeserializeOwned>(&self, endpoint: API) -> Result<T> {
+        let cost = endpoint.cost(&Method::POST, None);
         let url: String = format!("{}{}", self.host, String::from(endpoint));
 
         let client = &self.inner_client;
-        let response = client
+        let builder = client
             .post(url.as_str())
-            .headers(self.build_headers(false)?)
-            .send()
-            .await?;
+            .headers(self.build_headers(false)?);
 
-        self.handler(response).await
+        self.execute(builder, false, cost).await
     }
 
     pub async fn put<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
+        let cost = endpoint.cost(&Method::PUT, None);
         let url: String = format!("{}{}", self.host, String::from(endpoint));
         let data: String = format!("listenKey={}", listen_key);
 
         let client = &self.inner_client;
-        let response = client
+        let builder = client
             .put(url.as_str())
             .headers(self.build_headers(false)?)
-            .body(data)
-            .send()
-            .await?;
+            .body(data);
 
-        self.handler(response).await
+        self.execute(builder, true, cost).await
     }
 
     pub async fn delete<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
+        let cost = endpoint.cost(&Method::DELETE, None);
         let url: String = format!("{}{}", self.host, String::from(endpoint));
         let data: String = format!("listenKey={}", listen_key);
 
         let client = &self.inner_client;
-        let response = client
+        let builder = client
             .delete(url.as_str())
             .headers(self.build_headers(false)?)
-            .body(data)
-            .send()
-            .await?;
+            .body(data);
 
-        self.handler(response).await
+        self.execute(builder, false, cost).await
     }
 
-    // Request must be signed
-    fn sign_request(&self, endpoint: API, request: Option<String>) -> String {
-        match request {
-            Some(request) => {
-                let mut signed_key =
-                    Hmac::<Sha256>::new_varkey(self.secret_key.as_bytes()).unwrap();
-                signed_key.update(request.as_bytes());
-                let signature = hex_encode(signed_key.finalize().into_bytes());
-                let request_body: String = format!("{}&signature={}", request, signature);
-                format!("{}{}?{}", self.host, String::from(endpoint), request_body)
-            }
-            None => {
-                let signed_key = Hmac::<Sha256>::new_varkey(self.secret_key.as_bytes()).unwrap();
-                let signature = hex_encode(signed_key.finalize().into_bytes());
-                let request_body: String = format!("&signature={}", signature);
-                format!("{}{}?{}", self.host, String::from(endpoint), request_body)
-            }
-        }
-    }
-
-    fn build_headers(&self, content_type: bool) -> Result<HeaderMap> {
-        let mut custom_headers = HeaderMap::new();
-
-        custom_headers.insert(USER_AGENT, HeaderValue::from_static("binance-rs"));
-        if content_type {
-            custom_headers.insert(
-                CONTENT_TYPE,
-                HeaderValue::from_static("application/x-www-form-urlencoded"),
-            );
+    // Stamp, sign and send, re-syncing the server time once when Binance rejects the
+    // timestamp with -1021. The request was not processed then, so resending is safe.
+    async fn signed<T: DeserializeOwned>(
+        &self, method: Method, endpoint: API, request: Option<String>, retry: bool,
+    ) -> Result<T> {
+        let spot = matches!(endpoint, API::Spot(_));
+        if self.time_sync.is_stale(self.time_sync_interval) {
+            // A failed sync leaves the previous offset in place
+            let _ = self.sync_time(spot).await;
         }
-        custom_headers.insert(
-            HeaderName::from_static("x-mbx-apikey"),
-            HeaderValue::from_str(self.api_key.as_str())?,
-        );
 
-        Ok(custom_headers)
-    }
-
-    async fn handler<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
-        match response.status() {
-            StatusCode::OK => Ok(response.json::<T>().await?),
-            StatusCode::INTERNAL_SERVER_ERROR => {
-                bail!("Internal Server Error");
-            }
-            StatusCode::SERVICE_UNAVAILABLE => {
-                bail!("Service Unavailable");
-            }
-            StatusCode::UNAUTHORIZED => {
-                bail!("Unauthorized");
+        let cost = endpoint.cost(&method, request.as_deref());
+        let path = String::from(endpoint);
+        let mut resynced = false;
+
+        loop {
+            let request = match &request {
+                Some(request) => Some(self.time_sync.stamp(request)?),
+                None => None,
+            };
+            let url = self.sign_request(&path, request)?;
+            let builder = self
+                .inner_client
+                .request(method.clone(), url.as_str())
+                .headers(self.build_headers(true)?);
+
+            match self.execute(builder, retry, cost).await {
+                Err(Error(ErrorKind::BinanceError(ref error), _))
+                    if error.error_code() == BinanceErrorCode::InvalidTimestamp && !resynced => {}
+                result => return result,
             }
-            StatusCode::BAD_REQUEST => {
-                let error: BinanceContentError = response.json().await?;
-
-                Err(ErrorKind::BinanceError(error).into())
-            }
-            s => {
-                bail!(format!("Received response: {:?}", s));
-            }
-        }
-    }
-}
-
-#[cfg(feature = "blocking")]
-impl Client {
-    pub fn new(api_key: Option<String>, secret_key: Option<String>, host: String) -> Self {
-        Client {
-            api_key: api_key.unwrap_or_else(|| "".into()),
-            secret_key: secret_key.unwrap_or_else(|| "".into()),
-            host,
-            inner_client: reqwest::blocking::Client::builder()
-                .pool_idle_timeout(None)
-                .build()
-                .unwrap(),
-        }
-    }
-
-    pub fn get_signed<T: DeserializeOwned>(
-        &self, endpoint: API, request: Option<String>,
-    ) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let client = &self.inner_client;
-        let response = client
-            .get(url.as_str())
-            .headers(self.build_headers(true)?)
-            .send()?;
-
-        self.handler(response)
-    }
-
-    pub fn post_signed<T: DeserializeOwned>(&self, endpoint: API, request: String) -> Result<T> {
-        let url = self.sign_request(endpoint, Some(request));
-        let client = &self.inner_client;
-        let response = client
-            .post(url.as_str())
-            .headers(self.build_headers(true)?)
-            .send()?;
-
-        self.handler(response)
-    }
 
-    pub fn delete_signed<T: DeserializeOwned>(
-        &self, endpoint: API, request: Option<String>,
-    ) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let client = &self.inner_client;
-        let response = client
-            .delete(url.as_str())
-            .headers(self.build_headers(true)?)
-            .send()?;
-
-        self.handler(response)
-    }
-
-    pub fn get<T: DeserializeOwned>(&self, endpoint: API, request: Option<String>) -> Result<T> {
-        let mut url: String = format!("{}{}", self.host, String::from(endpoint));
-        if let Some(request) = request {
-            if !request.is_empty() {
-                url.push_str(format!("?{}", request).as_str());
-            }
+            // Should the sync fail, the resend returns the second -1021
+            resynced = true;
+            let _ = self.sync_time(spot).await;
         }
-
-        let client = &self.inner_client;
-        let response = client.get(url.as_str()).send()?;
-
-        self.handler(response)
-    }
-
-    pub fn post<T: DeserializeOwned>(&self, endpoint: API) -> Result<T> {
-        let url: String = format!("{}{}", self.host, String::from(endpoint));
-
-        let client = &self.inner_client;
-        let response = client
-            .post(url.as_str())
-            .headers(self.build_headers(false)?)
-            .send()?;
-
-        self.handler(response)
-    }
-
-    pub fn put<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
-        let url: String = format!("{}{}", self.host, String::from(endpoint));
-        let data: String = format!("listenKey={}", listen_key);
-
-        let client = &self.inner_client;
-        let response = client
-            .put(url.as_str())
-            .headers(self.build_headers(false)?)
-            .body(data)
-            .send()?;
-
-        self.handler(response)
     }
 
-    pub fn delete<T: DeserializeOwned>(&self, endpoint: API, listen_key: &str) -> Result<T> {
+    async fn sync_time(&self, spot: bool) -> Result<()> {
+        let endpoint = if spot {
+            API::Spot(Spot::Time)
+        } else {
+            API::Futures(Futures::Time)
+        };
+        let cost = endpoint.cost(&Method::GET, None);
         let url: String = format!("{}{}", self.host, String::from(endpoint));
-        let data: String = format!("listenKey={}", listen_key);
+        let builder = self.inner_client.get(url.as_str());
 
-        let client = &self.inner_client;
-        let response = client
-            .delete(url.as_str())
-            .headers(self.build_headers(false)?)
-            .body(data)
-            .send()?;
+        // Not retried, so a server that can't be reached doesn't hold up every
+        // signed request
+        let sent = SystemTime::now();
+        let server_time: ServerTime = self.execute(builder, false, cost).await?;
+        self.time_sync
+            .update(sent, SystemTime::now(), server_time.server_time);
 
-        self.handler(response)
+        Ok(())
     }
 
     // Request must be signed
-    fn sign_request(&self, endpoint: API, request: Option<String>) -> String {
-        match request {
-            Some(request) => {
-                let mut signed_key =
-                    Hmac::<Sha256>::new_varkey(self.secret_key.as_bytes()).unwrap();
-                signed_key.update(request.as_bytes());
-                let signature = hex_encode(signed_key.finalize().into_bytes());
-                let request_body: String = format!("{}&signature={}", request, signature);
-                format!("{}{}?{}", self.host, String::from(endpoint), request_body)
-            }
-            None => {
-                let signed_key = Hmac::<Sha256>::new_varkey(self.secret_key.as_bytes()).unwrap();
-                let signature = hex_encode(signed_key.finalize().into_bytes());
-                let request_body: String = format!("&signature={}", signature);
-                format!("{}{}?{}", self.host, String::from(endpoint), request_body)
-            }
-        }
+    fn sign_request(&self, path: &str, request: Option<String>) -> Result<String> {
+        let request = request.unwrap_or_default();
+        let signature = self.signer.sign(&request)?;
+
+        Ok(format!(
+            "{}{}?{}&signature={}",
+            self.host, path, request, signature
+        ))
     }
 
     fn build_headers(&self, content_type: bool) -> Result<HeaderMap> {
@@ -352,26 +233,99 @@ impl Client {
         Ok(custom_headers)
     }
 
-    fn handler<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
-        match response.status() {
-            StatusCode::OK => Ok(response.json::<T>()?),
-            StatusCode::INTERNAL_SERVER_ERROR => {
-                bail!("Internal Server Error");
-            }
-            StatusCode::SERVICE_UNAVAILABLE => {
-                bail!("Service Unavailable");
-            }
-            StatusCode::UNAUTHORIZED => {
-                bail!("Unauthorized");
+    // Send the request, retrying on 5xx, 429 and connection errors when `retry` is set.
+    // `cost` is reserved against the rate limits before each attempt.
+    async fn execute<T: DeserializeOwned>(
+        &self, builder: RequestBuilder, retry: bool, cost: Cost,
+    ) -> Result<T> {
+        let max_attempts = if retry {
+            self.retry_policy.max_attempts
+        } else {
+            1
+        };
+        let mut attempt = 1;
+
+        loop {
+            // Another request may take the freed capacity first, so check again
+            while let Some(wait) = self.rate_limits.acquire(&self.rate_limiter, cost)? {
+                tokio::time::sleep(wait).await;
             }
-            StatusCode::BAD_REQUEST => {
-                let error: BinanceContentError = response.json()?;
 
-                Err(ErrorKind::BinanceError(error).into())
-            }
-            s => {
-                bail!(format!("Received response: {:?}", s));
+            let pending = match builder.try_clone() {
+                Some(pending) if attempt < max_attempts => pending,
+                _ => {
+                    let response = builder.send().await?;
+                    self.rate_limits
+                        .update(response.status(), response.headers());
+                    return self.handler(response).await;
+                }
+            };
+
+            let retry_after = match pending.send().await {
+                Ok(response) => {
+                    self.rate_limits
+                        .update(response.status(), response.headers());
+                    if !is_retryable_status(response.status()) {
+                        return self.handler(response).await;
+                    }
+                    retry_after(response.headers())
+                }
+                Err(e) if e.is_connect() || e.is_timeout() => None,
+                Err(e) => return Err(e.into()),
+            };
+
+            tokio::time::sleep(self.retry_policy.backoff(attempt, retry_after)).await;
+            attempt += 1;
+        }
+    }
+
+    async fn handler<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
+        match response.status() {
+            StatusCode::OK => Ok(response.json::<T>().await?),
+            status => {
+                let retry_after = retry_after(response.headers());
+                let body = response.text().await?;
+
+                Err(response_error(status, retry_after, &body))
             }
         }
     }
 }
+
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+// Binance sends a JSON `{"code": .., "msg": ..}` body with most 4xx and 5xx responses
+pub(crate) fn response_error(
+    status: StatusCode, retry_after: Option<Duration>, body: &str,
+) -> Error {
+    let response: Option<BinanceContentError> = serde_json::from_str(body).ok();
+
+    match (status.as_u16(), response) {
+        (401, response) => ErrorKind::Unauthorized(response),
+        (403, response) => ErrorKind::Forbidden(response),
+        (418, response) => ErrorKind::IpBanned(response, retry_after),
+        (429, response) => ErrorKind::TooManyRequests(response, retry_after),
+        (500..=599, response) => ErrorKind::ServerError(status.as_u16(), response),
+        (_, Some(response)) => ErrorKind::BinanceError(response),
+        (status, None) => ErrorKind::UnexpectedStatus(status, body.into()),
+    }
+    .into()
+}
+
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+// Orders can only be safely resubmitted when Binance can deduplicate them, by the
+// client id of the order or, for order lists, of the list
+pub(crate) fn has_client_order_id(request: &str) -> bool {
+    request.split('&').any(|param| {
+        param.starts_with("newClientOrderId=") || param.starts_with("listClientOrderId=")
+    })
+}
@@ -1,3 +1,12 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+use crate::rate_limit::RateLimits;
+
+/// How often signed requests re-measure the server time offset unless configured
+/// otherwise.
+pub const DEFAULT_TIME_SYNC_INTERVAL: Duration = Duration::from_secs(300);
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Config {
     pub rest_api_endpoint: String,
@@ -7,10 +16,19 @@ pub struct Config {
     pub futures_ws_endpoint: String,
 
     pub recv_window: u64,
+    /// Re-measure the server time offset this often, `DEFAULT_TIME_SYNC_INTERVAL` by
+    /// default. `None` only syncs after a -1021 "Timestamp outside of the recvWindow"
+    /// error.
+    pub time_sync_interval: Option<Duration>,
+
+    pub retry_policy: RetryPolicy,
+
+    pub rate_limiter: RateLimiter,
+    pub rate_limits: Option<RateLimits>,
 }
 
-impl Config {
-    pub fn default() -> Config {
+impl Default for Config {
+    fn default() -> Config {
         Config {
             rest_api_endpoint: "https://api.binance.com".into(),
             ws_endpoint: "wss://stream.binance.com:9443/ws/".into(),
@@ -19,15 +37,23 @@ impl Config {
             futures_ws_endpoint: "wss://fstream.binance.com".into(),
 
             recv_window: 5000,
+            time_sync_interval: Some(DEFAULT_TIME_SYNC_INTERVAL),
+
+            retry_policy: RetryPolicy::default(),
+
+            rate_limiter: RateLimiter::Disabled,
+            rate_limits: None,
         }
     }
+}
 
+impl Config {
     pub fn testnet() -> Config {
         Config::default()
             .set_rest_api_endpoint("https://testnet.binance.vision/api")
             .set_ws_endpoint("wss://testnet.binance.vision/ws")
             .set_futures_rest_api_endpoint("https://testnet.binancefuture.com/api")
-            .set_futures_ws_endpoint("https://testnet.binancefuture.com/ws")
+            .set_futures_ws_endpoint("wss://stream.binancefuture.com")
     }
 
     pub fn set_rest_api_endpoint<T: Into<String>>(mut self, rest_api_endpoint: T) -> Self {
@@ -55,4 +81,215 @@ impl Config {
         self.recv_window = recv_window;
         self
     }
+
+    /// `None` turns the periodic sync off.
+    pub fn set_time_sync_interval<T: Into<Option<Duration>>>(
+        mut self, time_sync_interval: T,
+    ) -> Self {
+        self.time_sync_interval = time_sync_interval.into();
+        self
+    }
+
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn set_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Share one rate-limit state between every client built from this config.
+    /// Without it each client tracks its own usage.
+    pub fn set_rate_limits(mut self, rate_limits: RateLimits) -> Self {
+        self.rate_limits = Some(rate_limits);
+        self
+    }
+}
+
+/// What to do before sending a request whose weight (or new orders) would exceed one
+/// of the limits seeded from `ExchangeInformation::rate_limits`, or while a
+/// `Retry-After` from a 429/418 response is still running.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RateLimiter {
+    /// Only track usage.
+    Disabled,
+    /// Sleep until the window resets.
+    Delay,
+    /// Fail with `ErrorKind::RateLimitExceeded`.
+    Reject,
+}
+
+/// Retry behaviour for REST requests that fail with a 5xx, a 429 or a connection error.
+///
+/// Idempotent requests (GET, listen key keep-alive) are retried by default. Order
+/// placement is only retried when `retry_orders` is enabled *and* the request carries a
+/// `newClientOrderId` (or a `listClientOrderId` for order lists), so a duplicate
+/// submission is rejected by Binance instead of opening a second order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: u32,
+    /// Randomize each delay between zero and the computed backoff ("full jitter").
+    pub jitter: bool,
+    /// Wait for the duration sent in the `Retry-After` header when there is one.
+    pub respect_retry_after: bool,
+    pub retry_orders: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2,
+            jitter: true,
+            respect_retry_after: true,
+            retry_orders: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        RetryPolicy::default().set_max_attempts(1)
+    }
+
+    pub fn set_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn set_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn set_multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn set_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn set_respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    pub fn set_retry_orders(mut self, retry_orders: bool) -> Self {
+        self.retry_orders = retry_orders;
+        self
+    }
+
+    /// Delay to wait before the attempt following `attempt` (1-based).
+    pub fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after.filter(|_| self.respect_retry_after) {
+            return retry_after;
+        }
+
+        exponential_backoff(
+            self.initial_backoff,
+            self.max_backoff,
+            self.multiplier,
+            self.jitter,
+            attempt,
+        )
+    }
+}
+
+/// Reconnection of a websocket whose connection was closed or failed, as Binance does
+/// to every connection after 24 hours.
+///
+/// The connection is re-established with the same streams after a backoff, and the
+/// handler receives a `Disconnected` event before and a `Reconnected` event after.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReconnectPolicy {
+    /// Attempts per disconnection before giving up. `None` keeps trying.
+    pub max_attempts: Option<u32>,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: u32,
+    /// Randomize each delay between zero and the computed backoff ("full jitter").
+    pub jitter: bool,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy {
+            max_attempts: None,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            multiplier: 2,
+            jitter: true,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    pub fn set_max_attempts<A: Into<Option<u32>>>(mut self, max_attempts: A) -> Self {
+        self.max_attempts = max_attempts.into();
+        self
+    }
+
+    pub fn set_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn set_multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn set_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Delay to wait before reconnection attempt `attempt` (1-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        exponential_backoff(
+            self.initial_backoff,
+            self.max_backoff,
+            self.multiplier,
+            self.jitter,
+            attempt,
+        )
+    }
+
+    /// Whether another attempt may follow attempt `attempt`.
+    pub fn can_retry(&self, attempt: u32) -> bool {
+        !matches!(self.max_attempts, Some(max_attempts) if attempt >= max_attempts)
+    }
+}
+
+fn exponential_backoff(
+    initial_backoff: Duration, max_backoff: Duration, multiplier: u32, jitter: bool, attempt: u32,
+) -> Duration {
+    let factor = multiplier
+        .checked_pow(attempt.saturating_sub(1))
+        .unwrap_or(u32::MAX);
+    let backoff = initial_backoff
+        .checked_mul(factor)
+        .unwrap_or(max_backoff)
+        .min(max_backoff);
+
+    if jitter {
+        let random = RandomState::new().build_hasher().finish();
+        backoff.mul_f64((random % 1_000) as f64 / 1_000.0)
+    } else {
+        backoff
+    }
 }
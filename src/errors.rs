@@ -1,8 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BinanceContentError {
     pub code: i16,
     pub msg: String,
@@ -11,10 +12,273 @@ pub struct BinanceContentError {
     extra: HashMap<String, Value>,
 }
 
+impl BinanceContentError {
+    pub fn error_code(&self) -> BinanceErrorCode {
+        BinanceErrorCode::from(self.code)
+    }
+
+    // Details sent along with some errors, e.g. the outcome of a failed cancel-replace
+    pub(crate) fn data(&self) -> Option<&Value> {
+        self.extra.get("data")
+    }
+}
+
+/// Error codes documented in
+/// [errors.md](https://github.com/binance/binance-spot-api-docs/blob/master/errors.md) and
+/// the futures error code list.
+///
+/// -1000 to -1099 are general server or network issues, -1100 to -1199 request
+/// issues, -2000 to -2099 order and key rejections and -4000 to -4999 futures-specific
+/// errors. Codes without a variant are kept in `Other`. A few codes mean different
+/// things on spot and futures; their variants are named after both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinanceErrorCode {
+    // 10xx - General Server or Network issues
+    Unknown,
+    Disconnected,
+    Unauthorized,
+    TooManyRequests,
+    UnexpectedResponse,
+    Timeout,
+    InvalidMessage,
+    UnknownOrderComposition,
+    TooManyOrders,
+    ServiceShuttingDown,
+    UnsupportedOperation,
+    InvalidTimestamp,
+    InvalidSignature,
+
+    // 11xx - Request issues
+    IllegalChars,
+    TooManyParameters,
+    MandatoryParamEmptyOrMalformed,
+    UnknownParam,
+    UnreadParameters,
+    ParamEmpty,
+    ParamNotRequired,
+    BadPrecision,
+    NoDepth,
+    TimeInForceNotRequired,
+    InvalidTimeInForce,
+    InvalidOrderType,
+    InvalidSide,
+    EmptyNewClientOrderId,
+    EmptyOrigClientOrderId,
+    BadInterval,
+    BadSymbol,
+    InvalidListenKey,
+    MoreThanXxHours,
+    OptionalParamsBadCombo,
+    InvalidParameter,
+
+    // 20xx - Order and key rejections
+    NewOrderRejected,
+    CancelRejected,
+    NoSuchOrder,
+    BadApiKeyFormat,
+    RejectedMbxKey,
+    MarginNotSufficient,
+    /// -2021: the futures order would immediately trigger, or on spot the
+    /// cancel-replace partially failed.
+    ImmediateTriggerOrPartialCancelReplace,
+    /// -2022: the futures reduce-only order was rejected, or on spot the
+    /// cancel-replace failed.
+    ReduceOnlyOrCancelReplaceRejected,
+
+    // 40xx - Futures
+    InvalidOrderStatus,
+    PriceLessThanZero,
+    PriceGreaterThanMaxPrice,
+    QuantityLessThanZero,
+    QuantityLessThanMinQuantity,
+    QuantityGreaterThanMaxQuantity,
+    PriceNotIncreasedByTickSize,
+    QuantityNotIncreasedByStepSize,
+    NoNeedToChangeMarginType,
+    NoNeedToChangePositionSide,
+    PositionSideNotMatch,
+    MinNotional,
+
+    Other(i16),
+}
+
+impl BinanceErrorCode {
+    pub fn code(self) -> i16 {
+        use BinanceErrorCode::*;
+
+        match self {
+            Unknown => -1000,
+            Disconnected => -1001,
+            Unauthorized => -1002,
+            TooManyRequests => -1003,
+            UnexpectedResponse => -1006,
+            Timeout => -1007,
+            InvalidMessage => -1013,
+            UnknownOrderComposition => -1014,
+            TooManyOrders => -1015,
+            ServiceShuttingDown => -1016,
+            UnsupportedOperation => -1020,
+            InvalidTimestamp => -1021,
+            InvalidSignature => -1022,
+            IllegalChars => -1100,
+            TooManyParameters => -1101,
+            MandatoryParamEmptyOrMalformed => -1102,
+            UnknownParam => -1103,
+            UnreadParameters => -1104,
+            ParamEmpty => -1105,
+            ParamNotRequired => -1106,
+            BadPrecision => -1111,
+            NoDepth => -1112,
+            TimeInForceNotRequired => -1114,
+            InvalidTimeInForce => -1115,
+            InvalidOrderType => -1116,
+            InvalidSide => -1117,
+            EmptyNewClientOrderId => -1118,
+            EmptyOrigClientOrderId => -1119,
+            BadInterval => -1120,
+            BadSymbol => -1121,
+            InvalidListenKey => -1125,
+            MoreThanXxHours => -1127,
+            OptionalParamsBadCombo => -1128,
+            InvalidParameter => -1130,
+            NewOrderRejected => -2010,
+            CancelRejected => -2011,
+            NoSuchOrder => -2013,
+            BadApiKeyFormat => -2014,
+            RejectedMbxKey => -2015,
+            MarginNotSufficient => -2019,
+            ImmediateTriggerOrPartialCancelReplace => -2021,
+            ReduceOnlyOrCancelReplaceRejected => -2022,
+            InvalidOrderStatus => -4000,
+            PriceLessThanZero => -4001,
+            PriceGreaterThanMaxPrice => -4002,
+            QuantityLessThanZero => -4003,
+            QuantityLessThanMinQuantity => -4004,
+            QuantityGreaterThanMaxQuantity => -4005,
+            PriceNotIncreasedByTickSize => -4014,
+            QuantityNotIncreasedByStepSize => -4023,
+            NoNeedToChangeMarginType => -4046,
+            NoNeedToChangePositionSide => -4059,
+            PositionSideNotMatch => -4061,
+            MinNotional => -4164,
+            Other(code) => code,
+        }
+    }
+
+    /// -1000 to -1099
+    pub fn is_general(self) -> bool {
+        (-1099..=-1000).contains(&self.code())
+    }
+
+    /// -1100 to -1199
+    pub fn is_request_issue(self) -> bool {
+        (-1199..=-1100).contains(&self.code())
+    }
+
+    /// -4000 to -4999
+    pub fn is_futures(self) -> bool {
+        (-4999..=-4000).contains(&self.code())
+    }
+}
+
+impl From<i16> for BinanceErrorCode {
+    fn from(code: i16) -> Self {
+        use BinanceErrorCode::*;
+
+        match code {
+            -1000 => Unknown,
+            -1001 => Disconnected,
+            -1002 => Unauthorized,
+            -1003 => TooManyRequests,
+            -1006 => UnexpectedResponse,
+            -1007 => Timeout,
+            -1013 => InvalidMessage,
+            -1014 => UnknownOrderComposition,
+            -1015 => TooManyOrders,
+            -1016 => ServiceShuttingDown,
+            -1020 => UnsupportedOperation,
+            -1021 => InvalidTimestamp,
+            -1022 => InvalidSignature,
+            -1100 => IllegalChars,
+            -1101 => TooManyParameters,
+            -1102 => MandatoryParamEmptyOrMalformed,
+            -1103 => UnknownParam,
+            -1104 => UnreadParameters,
+            -1105 => ParamEmpty,
+            -1106 => ParamNotRequired,
+            -1111 => BadPrecision,
+            -1112 => NoDepth,
+            -1114 => TimeInForceNotRequired,
+            -1115 => InvalidTimeInForce,
+            -1116 => InvalidOrderType,
+            -1117 => InvalidSide,
+            -1118 => EmptyNewClientOrderId,
+            -1119 => EmptyOrigClientOrderId,
+            -1120 => BadInterval,
+            -1121 => BadSymbol,
+            -1125 => InvalidListenKey,
+            -1127 => MoreThanXxHours,
+            -1128 => OptionalParamsBadCombo,
+            -1130 => InvalidParameter,
+            -2010 => NewOrderRejected,
+            -2011 => CancelRejected,
+            -2013 => NoSuchOrder,
+            -2014 => BadApiKeyFormat,
+            -2015 => RejectedMbxKey,
+            -2019 => MarginNotSufficient,
+            -2021 => ImmediateTriggerOrPartialCancelReplace,
+            -2022 => ReduceOnlyOrCancelReplaceRejected,
+            -4000 => InvalidOrderStatus,
+            -4001 => PriceLessThanZero,
+            -4002 => PriceGreaterThanMaxPrice,
+            -4003 => QuantityLessThanZero,
+            -4004 => QuantityLessThanMinQuantity,
+            -4005 => QuantityGreaterThanMaxQuantity,
+            -4014 => PriceNotIncreasedByTickSize,
+            -4023 => QuantityNotIncreasedByStepSize,
+            -4046 => NoNeedToChangeMarginType,
+            -4059 => NoNeedToChangePositionSide,
+            -4061 => PositionSideNotMatch,
+            -4164 => MinNotional,
+            code => Other(code),
+        }
+    }
+}
+
 error_chain! {
     errors {
         BinanceError(response: BinanceContentError)
         ListenKeyExpired
+        RateLimitExceeded(retry_in: Duration) {
+            description("rate limit exceeded")
+            display("Rate limit exceeded, retry in {:?}", retry_in)
+        }
+        Unauthorized(response: Option<BinanceContentError>) {
+            description("unauthorized")
+            display("Unauthorized: {:?}", response)
+        }
+        // WAF limit violated
+        Forbidden(response: Option<BinanceContentError>) {
+            description("forbidden")
+            display("Forbidden: {:?}", response)
+        }
+        TooManyRequests(response: Option<BinanceContentError>, retry_after: Option<Duration>) {
+            description("too many requests")
+            display("Too many requests, retry after {:?}: {:?}", retry_after, response)
+        }
+        IpBanned(response: Option<BinanceContentError>, retry_after: Option<Duration>) {
+            description("IP banned")
+            display("IP banned, retry after {:?}: {:?}", retry_after, response)
+        }
+        // The execution status of the request is unknown
+        ServerError(status: u16, response: Option<BinanceContentError>) {
+            description("server error")
+            display("Server error {}: {:?}", status, response)
+        }
+        UnexpectedStatus(status: u16, body: String) {
+            description("unexpected status")
+            display("Received response {}: {}", status, body)
+        }
      }
 
     foreign_links {
@@ -28,3 +292,62 @@ error_chain! {
         TimestampError(std::time::SystemTimeError);
     }
 }
+
+impl Error {
+    /// The Binance error code from the response body, if there was one.
+    pub fn binance_code(&self) -> Option<BinanceErrorCode> {
+        match self.kind() {
+            ErrorKind::BinanceError(response) => Some(response.error_code()),
+            ErrorKind::Unauthorized(response)
+            | ErrorKind::Forbidden(response)
+            | ErrorKind::TooManyRequests(response, _)
+            | ErrorKind::IpBanned(response, _)
+            | ErrorKind::ServerError(_, response) => {
+                response.as_ref().map(BinanceContentError::error_code)
+            }
+            _ => None,
+        }
+    }
+
+    /// Hit a Binance or client-side rate limit. Wait for `retry_after` before the next request.
+    pub fn is_rate_limited(&self) -> bool {
+        match self.kind() {
+            ErrorKind::TooManyRequests(..)
+            | ErrorKind::IpBanned(..)
+            | ErrorKind::RateLimitExceeded(_) => true,
+            _ => matches!(
+                self.binance_code(),
+                Some(BinanceErrorCode::TooManyRequests) | Some(BinanceErrorCode::TooManyOrders)
+            ),
+        }
+    }
+
+    /// The same request may succeed when sent again later.
+    pub fn is_retryable(&self) -> bool {
+        match self.kind() {
+            ErrorKind::ServerError(..) => true,
+            ErrorKind::ReqError(e) => e.is_connect() || e.is_timeout(),
+            _ if self.is_rate_limited() => true,
+            _ => matches!(
+                self.binance_code(),
+                Some(BinanceErrorCode::Unknown)
+                    | Some(BinanceErrorCode::Disconnected)
+                    | Some(BinanceErrorCode::UnexpectedResponse)
+                    | Some(BinanceErrorCode::Timeout)
+                    | Some(BinanceErrorCode::ServiceShuttingDown)
+                    | Some(BinanceErrorCode::InvalidTimestamp)
+            ),
+        }
+    }
+
+    /// How long Binance asked to wait before the next request.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self.kind() {
+            ErrorKind::TooManyRequests(_, retry_after) | ErrorKind::IpBanned(_, retry_after) => {
+                *retry_after
+            }
+            ErrorKind::RateLimitExceeded(retry_in) => Some(*retry_in),
+            _ => None,
+        }
+    }
+}
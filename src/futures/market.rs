@@ -25,7 +25,9 @@ use crate::futures::model::*;
 use crate::client::*;
 use crate::errors::*;
 use std::collections::BTreeMap;
-use serde_json::Value;
+use crate::order_book::{DepthUpdate, LocalOrderBook, UpdateStatus, SNAPSHOT_DEPTH};
+use crate::pagination::{pages, Cursor, Pager, FUTURES_KLINES_LIMIT, TRADES_LIMIT};
+use futures_util::stream::Stream;
 use crate::api::API;
 use crate::api::Futures;
 
@@ -40,149 +42,9 @@ pub struct FuturesMarket {
     pub recv_window: u64,
 }
 
-#[cfg(feature = "blocking")]
 impl FuturesMarket {
     // Order book (Default 100; max 1000)
-    pub fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-
-        parameters.insert("symbol".into(), symbol.into());
-        let request = build_request(&parameters);
-
-        self.client.get(API::Futures(Futures::Depth), Some(request))
-    }
-
-    pub fn get_trades<S>(&self, symbol: S) -> Result<Trades>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        let request = build_request(&parameters);
-        self.client
-            .get(API::Futures(Futures::Trades), Some(request))
-    }
-
-    // TODO This may be incomplete, as it hasn't been tested
-    pub fn get_historical_trades<S1, S2, S3>(
-        &self, symbol: S1, from_id: S2, limit: S3,
-    ) -> Result<Trades>
-    where
-        S1: Into<String>,
-        S2: Into<Option<u64>>,
-        S3: Into<Option<u16>>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-
-        parameters.insert("symbol".into(), symbol.into());
-
-        // Add three optional parameters
-        if let Some(lt) = limit.into() {
-            parameters.insert("limit".into(), format!("{}", lt));
-        }
-        if let Some(fi) = from_id.into() {
-            parameters.insert("fromId".into(), format!("{}", fi));
-        }
-
-        let request = build_signed_request(parameters, self.recv_window)?;
-
-        self.client
-            .get_signed(API::Futures(Futures::HistoricalTrades), Some(request))
-    }
-
-    pub fn get_agg_trades<S1, S2, S3, S4, S5>(
-        &self, symbol: S1, from_id: S2, start_time: S3, end_time: S4, limit: S5,
-    ) -> Result<AggTrades>
-    where
-        S1: Into<String>,
-        S2: Into<Option<u64>>,
-        S3: Into<Option<u64>>,
-        S4: Into<Option<u64>>,
-        S5: Into<Option<u16>>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-
-        parameters.insert("symbol".into(), symbol.into());
-
-        // Add three optional parameters
-        if let Some(lt) = limit.into() {
-            parameters.insert("limit".into(), format!("{}", lt));
-        }
-        if let Some(st) = start_time.into() {
-            parameters.insert("startTime".into(), format!("{}", st));
-        }
-        if let Some(et) = end_time.into() {
-            parameters.insert("endTime".into(), format!("{}", et));
-        }
-        if let Some(fi) = from_id.into() {
-            parameters.insert("fromId".into(), format!("{}", fi));
-        }
-
-        let request = build_request(&parameters);
-
-        self.client
-            .get(API::Futures(Futures::AggTrades), Some(request))
-    }
-
-    // Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
-    // https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#klinecandlestick-data
-    pub fn get_klines<S1, S2, S3, S4, S5>(
-        &self, symbol: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
-    ) -> Result<KlineSummaries>
-    where
-        S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<Option<u16>>,
-        S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("interval".into(), interval.into());
-
-        // Add three optional parameters
-        if let Some(lt) = limit.into() {
-            parameters.insert("limit".into(), format!("{}", lt));
-        }
-        if let Some(st) = start_time.into() {
-            parameters.insert("startTime".into(), format!("{}", st));
-        }
-        if let Some(et) = end_time.into() {
-            parameters.insert("endTime".into(), format!("{}", et));
-        }
-
-        let request = build_request(&parameters);
-
-        let data: Vec<Vec<Value>> = self
-            .client
-            .get(API::Futures(Futures::Klines), Some(request))?;
-
-        let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_f64(&row[1]),
-                    high: to_f64(&row[2]),
-                    low: to_f64(&row[3]),
-                    close: to_f64(&row[4]),
-                    volume: to_f64(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_f64(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_f64(&row[9]),
-                    taker_buy_quote_asset_volume: to_f64(&row[10]),
-                })
-                .collect(),
-        );
-        Ok(klines)
-    }
-
-    // 24hr ticker price change statistics
-    pub fn get_24h_price_stats<S>(&self, symbol: S) -> Result<PriceStats>
+    pub async fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
     where
         S: Into<String>,
     {
@@ -192,76 +54,42 @@ impl FuturesMarket {
         let request = build_request(&parameters);
 
         self.client
-            .get(API::Futures(Futures::Ticker24hr), Some(request))
+            .get(API::Futures(Futures::Depth), Some(request))
+            .await
     }
 
-    // Latest price for ONE symbol.
-    pub fn get_price<S>(&self, symbol: S) -> Result<SymbolPrice>
+    // Order book at a custom depth. Currently supported values
+    // are 5, 10, 20, 50, 100, 500, 1000
+    pub async fn get_custom_depth<S>(&self, symbol: S, depth: u64) -> Result<OrderBook>
     where
         S: Into<String>,
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
 
         parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("limit".into(), depth.to_string());
         let request = build_request(&parameters);
 
         self.client
-            .get(API::Futures(Futures::TickerPrice), Some(request))
-    }
-
-    // Symbols order book ticker
-    // -> Best price/qty on the order book for ALL symbols.
-    pub fn get_all_book_tickers(&self) -> Result<BookTickers> {
-        self.client.get(API::Futures(Futures::BookTicker), None)
-    }
-
-    // -> Best price/qty on the order book for ONE symbol
-    pub fn get_book_ticker<S>(&self, symbol: S) -> Result<Tickers>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        let request = build_request(&parameters);
-        self.client
-            .get(API::Futures(Futures::BookTicker), Some(request))
-    }
-
-    pub fn get_mark_prices(&self) -> Result<MarkPrices> {
-        self.client.get(API::Futures(Futures::PremiumIndex), None)
-    }
-
-    pub fn get_all_liquidation_orders(&self) -> Result<LiquidationOrders> {
-        self.client.get(API::Futures(Futures::AllForceOrders), None)
-    }
-
-    pub fn open_interest<S>(&self, symbol: S) -> Result<OpenInterest>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        let request = build_request(&parameters);
-        self.client
-            .get(API::Futures(Futures::OpenInterest), Some(request))
+            .get(API::Futures(Futures::Depth), Some(request))
+            .await
     }
-}
 
-#[cfg(not(feature = "blocking"))]
-impl FuturesMarket {
-    // Order book (Default 100; max 1000)
-    pub async fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
+    /// Apply a depth update to `book`, fetching a snapshot when the book needs one:
+    /// before the first update is applied and after a missed update.
+    pub async fn update_order_book<U>(
+        &self, book: &mut LocalOrderBook, update: U,
+    ) -> Result<UpdateStatus>
     where
-        S: Into<String>,
+        U: Into<DepthUpdate>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-
-        parameters.insert("symbol".into(), symbol.into());
-        let request = build_request(&parameters);
+        let status = book.push(update);
+        if book.needs_snapshot() {
+            let snapshot = self.get_custom_depth(book.symbol(), SNAPSHOT_DEPTH).await?;
+            book.apply_snapshot(snapshot);
+        }
 
-        self.client
-            .get(API::Futures(Futures::Depth), Some(request))
-            .await
+        Ok(status)
     }
 
     pub async fn get_trades<S>(&self, symbol: S) -> Result<Trades>
@@ -339,17 +167,16 @@ impl FuturesMarket {
             .await
     }
 
-    // Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
+    // Returns up to 'limit' klines for given symbol and interval
     // https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#klinecandlestick-data
-    pub async fn get_klines<S1, S2, S3, S4, S5>(
-        &self, symbol: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
+    pub async fn get_klines<S1, S2, S3, S4>(
+        &self, symbol: S1, interval: KlineInterval, limit: S2, start_time: S3, end_time: S4,
     ) -> Result<KlineSummaries>
     where
         S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<Option<u16>>,
+        S2: Into<Option<u16>>,
+        S3: Into<Option<u64>>,
         S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
 
@@ -368,30 +195,77 @@ impl FuturesMarket {
         }
 
         let request = build_request(&parameters);
-
-        let data: Vec<Vec<Value>> = self
+        let klines: Vec<KlineSummary> = self
             .client
             .get(API::Futures(Futures::Klines), Some(request))
             .await?;
 
-        let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_f64(&row[1]),
-                    high: to_f64(&row[2]),
-                    low: to_f64(&row[3]),
-                    close: to_f64(&row[4]),
-                    volume: to_f64(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_f64(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_f64(&row[9]),
-                    taker_buy_quote_asset_volume: to_f64(&row[10]),
-                })
-                .collect(),
+        Ok(KlineSummaries::AllKlineSummaries(klines))
+    }
+
+    /// All klines opened from `start_time` to `end_time`, or until now without it, as
+    /// a `Stream` fetching 1500 at a time.
+    pub fn get_klines_range<S1, S2>(
+        &self, symbol: S1, interval: KlineInterval, start_time: u64, end_time: S2,
+    ) -> impl Stream<Item = Result<KlineSummary>> + '_
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+    {
+        let symbol: String = symbol.into();
+        let end_time = end_time.into();
+        let pager = Pager::new(
+            Cursor::StartTime(start_time),
+            end_time,
+            FUTURES_KLINES_LIMIT,
         );
-        Ok(klines)
+
+        pages(pager, move |cursor| {
+            let symbol = symbol.clone();
+            async move {
+                let limit = format!("limit={}", FUTURES_KLINES_LIMIT);
+                self.client
+                    .wait_for_weight(&API::Futures(Futures::Klines), &limit)
+                    .await?;
+                let KlineSummaries::AllKlineSummaries(klines) = self
+                    .get_klines(
+                        symbol,
+                        interval,
+                        FUTURES_KLINES_LIMIT,
+                        cursor.time(),
+                        end_time,
+                    )
+                    .await?;
+                Ok(klines)
+            }
+        })
+    }
+
+    /// All aggregate trades from `start_time` to `end_time`, or until now without it,
+    /// as a `Stream` fetching 1000 at a time.
+    pub fn get_agg_trades_range<S1, S2>(
+        &self, symbol: S1, start_time: u64, end_time: S2,
+    ) -> impl Stream<Item = Result<AggTrade>> + '_
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+    {
+        let symbol: String = symbol.into();
+        let pager = Pager::new(Cursor::StartTime(start_time), end_time.into(), TRADES_LIMIT);
+
+        pages(pager, move |cursor| {
+            let symbol = symbol.clone();
+            async move {
+                let limit = format!("limit={}", TRADES_LIMIT);
+                self.client
+                    .wait_for_weight(&API::Futures(Futures::AggTrades), &limit)
+                    .await?;
+                let AggTrades::AllAggTrades(trades) = self
+                    .get_agg_trades(symbol, cursor.id(), cursor.time(), None, TRADES_LIMIT)
+                    .await?;
+                Ok(trades)
+            }
+        })
     }
 
     // 24hr ticker price change statistics
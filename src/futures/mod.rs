@@ -5,43 +5,69 @@ pub mod model;
 pub mod userstream;
 pub mod websockets;
 
-fn round(base: f64, precision: i32) -> f64 {
+use model::Amount;
+
+#[cfg(not(feature = "decimal"))]
+fn round(base: Amount, precision: i32) -> Amount {
     let multiplier = 10.0_f64.powi(precision);
     (base * multiplier).round() / multiplier
 }
 
-fn floor(base: f64, precision: i32) -> f64 {
+#[cfg(not(feature = "decimal"))]
+fn floor(base: Amount, precision: i32) -> Amount {
     let multiplier = 10.0_f64.powi(precision);
     (base * multiplier).floor() / multiplier
 }
 
-fn ceil(base: f64, precision: i32) -> f64 {
+#[cfg(not(feature = "decimal"))]
+fn ceil(base: Amount, precision: i32) -> Amount {
     let multiplier = 10.0_f64.powi(precision);
     (base * multiplier).ceil() / multiplier
 }
 
+#[cfg(feature = "decimal")]
+fn round(base: Amount, precision: i32) -> Amount {
+    use rust_decimal::RoundingStrategy;
+
+    base.round_dp_with_strategy(precision as u32, RoundingStrategy::MidpointAwayFromZero)
+}
+
+#[cfg(feature = "decimal")]
+fn floor(base: Amount, precision: i32) -> Amount {
+    use rust_decimal::RoundingStrategy;
+
+    base.round_dp_with_strategy(precision as u32, RoundingStrategy::ToNegativeInfinity)
+}
+
+#[cfg(feature = "decimal")]
+fn ceil(base: Amount, precision: i32) -> Amount {
+    use rust_decimal::RoundingStrategy;
+
+    base.round_dp_with_strategy(precision as u32, RoundingStrategy::ToPositiveInfinity)
+}
+
 impl model::Symbol {
-    pub fn round_price(&self, price: f64) -> f64 {
+    pub fn round_price(&self, price: Amount) -> Amount {
         round(price, self.price_precision as i32)
     }
 
-    pub fn round_quantity(&self, qty: f64) -> f64 {
+    pub fn round_quantity(&self, qty: Amount) -> Amount {
         round(qty, self.quantity_precision as i32)
     }
 
-    pub fn floor_price(&self, price: f64) -> f64 {
+    pub fn floor_price(&self, price: Amount) -> Amount {
         floor(price, self.price_precision as i32)
     }
 
-    pub fn floor_quantity(&self, qty: f64) -> f64 {
+    pub fn floor_quantity(&self, qty: Amount) -> Amount {
         floor(qty, self.quantity_precision as i32)
     }
 
-    pub fn ceil_price(&self, price: f64) -> f64 {
+    pub fn ceil_price(&self, price: Amount) -> Amount {
         ceil(price, self.price_precision as i32)
     }
 
-    pub fn ceil_quantity(&self, qty: f64) -> f64 {
+    pub fn ceil_quantity(&self, qty: Amount) -> Amount {
         ceil(qty, self.quantity_precision as i32)
     }
 }
@@ -2,8 +2,9 @@ use serde::{Deserialize, Serialize};
 use crate::model::{string_or_float, string_or_bool, string_or_integer, optional_string_or_float};
 
 pub use crate::model::{
-    Asks, Bids, BookTickers, Filters, KlineSummaries, KlineSummary, RateLimit, ServerTime,
-    SymbolPrice, Tickers,
+    Amount, Asks, Bids, BookTickers, ExecutionType, Filters, KlineInterval, KlineSummaries,
+    KlineSummary, OrderSide, OrderStatus, OrderType, PositionSide, RateLimit, ServerTime,
+    SymbolPrice, Tickers, TimeInForce,
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -30,8 +31,8 @@ pub struct Symbol {
     pub base_asset_precision: u64,
     pub quote_precision: u64,
     pub filters: Vec<Filters>,
-    pub order_types: Vec<String>,
-    pub time_in_force: Vec<String>,
+    pub order_types: Vec<OrderType>,
+    pub time_in_force: Vec<TimeInForce>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -56,19 +57,19 @@ pub struct PriceStats {
     pub price_change_percent: String,
     pub weighted_avg_price: String,
     #[serde(with = "string_or_float")]
-    pub last_price: f64,
+    pub last_price: Amount,
     #[serde(with = "string_or_float")]
-    pub open_price: f64,
+    pub open_price: Amount,
     #[serde(with = "string_or_float")]
-    pub high_price: f64,
+    pub high_price: Amount,
     #[serde(with = "string_or_float")]
-    pub low_price: f64,
+    pub low_price: Amount,
     #[serde(with = "string_or_float")]
-    pub volume: f64,
+    pub volume: Amount,
     #[serde(with = "string_or_float")]
-    pub quote_volume: f64,
+    pub quote_volume: Amount,
     #[serde(with = "string_or_float")]
-    pub last_qty: f64,
+    pub last_qty: Amount,
     pub open_time: u64,
     pub close_time: u64,
     pub first_id: u64,
@@ -88,11 +89,11 @@ pub struct Trade {
     pub id: u64,
     pub is_buyer_maker: bool,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
     #[serde(with = "string_or_float")]
-    pub quote_qty: f64,
+    pub quote_qty: Amount,
     pub time: u64,
 }
 
@@ -116,9 +117,9 @@ pub struct AggTrade {
     #[serde(rename = "m")]
     pub maker: bool,
     #[serde(rename = "p", with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(rename = "q", with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -132,9 +133,9 @@ pub enum MarkPrices {
 pub struct MarkPrice {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub mark_price: f64,
+    pub mark_price: Amount,
     #[serde(with = "string_or_float")]
-    pub last_funding_rate: f64,
+    pub last_funding_rate: Amount,
     pub next_funding_time: u64,
     pub time: u64,
 }
@@ -149,26 +150,26 @@ pub enum LiquidationOrders {
 #[serde(rename_all = "camelCase")]
 pub struct LiquidationOrder {
     #[serde(with = "string_or_float")]
-    pub average_price: f64,
+    pub average_price: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub price: f64,
-    pub side: String,
-    pub status: String,
+    pub price: Amount,
+    pub side: OrderSide,
+    pub status: OrderStatus,
     pub symbol: String,
     pub time: u64,
-    pub time_in_force: String,
-    pub r#type: String,
+    pub time_in_force: TimeInForce,
+    pub r#type: OrderType,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenInterest {
     #[serde(with = "string_or_float")]
-    pub open_interest: f64,
+    pub open_interest: Amount,
     pub symbol: String,
 }
 
@@ -181,20 +182,20 @@ pub struct Order {
     pub executed_qty: String,
     pub order_id: u64,
     pub orig_qty: String,
-    pub orig_type: String,
+    pub orig_type: OrderType,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     pub reduce_only: bool,
-    pub side: String,
-    pub position_side: String,
-    pub status: String,
+    pub side: OrderSide,
+    pub position_side: PositionSide,
+    pub status: OrderStatus,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     pub close_position: bool,
     pub symbol: String,
     pub time: u64,
     #[serde(rename = "type")]
-    pub type_name: String,
+    pub type_name: OrderType,
     pub update_time: u64,
     pub working_type: String,
     pub price_protect: bool,
@@ -209,19 +210,19 @@ pub struct PlacedOrder {
     pub executed_qty: String,
     pub order_id: u64,
     pub orig_qty: String,
-    pub orig_type: String,
+    pub orig_type: OrderType,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     pub reduce_only: bool,
-    pub side: String,
-    pub position_side: String,
-    pub status: String,
+    pub side: OrderSide,
+    pub position_side: PositionSide,
+    pub status: OrderStatus,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     pub close_position: bool,
     pub symbol: String,
     #[serde(rename = "type")]
-    pub type_name: String,
+    pub type_name: OrderType,
     pub update_time: u64,
     pub working_type: String,
     pub price_protect: bool,
@@ -233,15 +234,15 @@ pub struct AccountBalance {
     pub account_alias: String,
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub balance: f64,
+    pub balance: Amount,
     #[serde(with = "string_or_float")]
-    pub cross_wallet_balance: f64,
+    pub cross_wallet_balance: Amount,
     #[serde(with = "string_or_float")]
-    pub cross_un_pnl: f64,
+    pub cross_un_pnl: Amount,
     #[serde(with = "string_or_float")]
-    pub available_balance: f64,
+    pub available_balance: Amount,
     #[serde(with = "string_or_float")]
-    pub max_withdraw_amount: f64,
+    pub max_withdraw_amount: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -249,7 +250,7 @@ pub struct AccountBalance {
 pub struct Leverage {
     pub leverage: u64,
     #[serde(with = "string_or_float")]
-    pub max_notional_value: f64,
+    pub max_notional_value: Amount,
     pub symbol: String,
 }
 
@@ -264,24 +265,24 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct Position {
     #[serde(with = "string_or_float")]
-    pub entry_price: f64,
+    pub entry_price: Amount,
     pub margin_type: String,
     #[serde(with = "string_or_bool")]
     pub is_auto_add_margin: bool,
     #[serde(with = "string_or_integer")]
     pub leverage: u64,
     #[serde(with = "string_or_float")]
-    pub liquidation_price: f64,
+    pub liquidation_price: Amount,
     #[serde(with = "string_or_float")]
-    pub mark_price: f64,
+    pub mark_price: Amount,
     #[serde(with = "string_or_float")]
-    pub max_notional_value: f64,
+    pub max_notional_value: Amount,
     #[serde(with = "string_or_float")]
-    pub position_amt: f64,
+    pub position_amt: Amount,
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub un_realized_profit: f64,
-    pub position_side: String,
+    pub un_realized_profit: Amount,
+    pub position_side: PositionSide,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -305,44 +306,44 @@ pub struct OrderTradeUpdate {
     #[serde(rename = "c")]
     pub client_order_id: String,
     #[serde(rename = "S")]
-    pub side: String,
+    pub side: OrderSide,
     #[serde(rename = "o")]
-    pub order_type: String,
+    pub order_type: OrderType,
     #[serde(rename = "f")]
-    pub time_in_force: String,
+    pub time_in_force: TimeInForce,
     #[serde(rename = "q", with = "string_or_float")]
-    pub original_quantity: f64,
+    pub original_quantity: Amount,
     #[serde(rename = "p", with = "string_or_float")]
-    pub original_price: f64,
+    pub original_price: Amount,
     #[serde(rename = "ap", with = "string_or_float")]
-    pub average_price: f64,
+    pub average_price: Amount,
     #[serde(rename = "sp", with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     #[serde(rename = "x")]
-    pub execution_type: String,
+    pub execution_type: ExecutionType,
     #[serde(rename = "X")]
-    pub order_status: String,
+    pub order_status: OrderStatus,
     #[serde(rename = "i")]
     pub order_id: u64,
     #[serde(rename = "l", with = "string_or_float")]
-    pub last_filled_quantity: f64,
+    pub last_filled_quantity: Amount,
     #[serde(rename = "z", with = "string_or_float")]
-    pub filled_accumulated_quantity: f64,
+    pub filled_accumulated_quantity: Amount,
     #[serde(rename = "L", with = "string_or_float")]
-    pub last_filled_price: f64,
+    pub last_filled_price: Amount,
     #[serde(rename = "N")]
     pub commission_asset: Option<String>,
     #[serde(default)]
     #[serde(rename = "n", with = "optional_string_or_float")]
-    pub commission: Option<f64>,
+    pub commission: Option<Amount>,
     #[serde(rename = "T")]
     pub trade_time: u64,
     #[serde(rename = "t")]
     pub trade_id: u64,
     #[serde(rename = "b", with = "string_or_float")]
-    pub bids_notional: f64,
+    pub bids_notional: Amount,
     #[serde(rename = "a", with = "string_or_float")]
-    pub ask_notional: f64,
+    pub ask_notional: Amount,
     #[serde(rename = "m")]
     pub maker_side: bool,
     #[serde(rename = "R")]
@@ -350,19 +351,19 @@ pub struct OrderTradeUpdate {
     #[serde(rename = "wt")]
     pub stop_price_working_type: String,
     #[serde(rename = "ot")]
-    pub original_order_type: String,
+    pub original_order_type: OrderType,
     #[serde(rename = "ps")]
-    pub position_side: String,
+    pub position_side: PositionSide,
     #[serde(rename = "cp")]
     pub cp: Option<bool>,
     #[serde(default)]
     #[serde(rename = "AP", with = "optional_string_or_float")]
-    pub activation_price: Option<f64>,
+    pub activation_price: Option<Amount>,
     #[serde(default)]
     #[serde(rename = "cr", with = "optional_string_or_float")]
-    pub callback_rate: Option<f64>,
+    pub callback_rate: Option<Amount>,
     #[serde(rename = "rp", with = "string_or_float")]
-    pub realized_profit: f64,
+    pub realized_profit: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -395,9 +396,9 @@ pub struct Balance {
     #[serde(rename = "a")]
     pub asset: String,
     #[serde(rename = "wb", with = "string_or_float")]
-    pub wallet_balance: f64,
+    pub wallet_balance: Amount,
     #[serde(rename = "cw", with = "string_or_float")]
-    pub cross_wallet_balance: f64,
+    pub cross_wallet_balance: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -406,19 +407,19 @@ pub struct WSPosition {
     #[serde(rename = "s")]
     pub symbol: String,
     #[serde(rename = "pa", with = "string_or_float")]
-    pub position_amount: f64,
+    pub position_amount: Amount,
     #[serde(rename = "ep", with = "string_or_float")]
-    pub entry_price: f64,
+    pub entry_price: Amount,
     #[serde(rename = "cr", with = "string_or_float")]
-    pub accumulated_realized: f64,
+    pub accumulated_realized: Amount,
     #[serde(rename = "up", with = "string_or_float")]
-    pub unrealized_pnl: f64,
+    pub unrealized_pnl: Amount,
     #[serde(rename = "mt")]
     pub margin_tyoe: String,
     #[serde(rename = "iw", with = "string_or_float")]
-    pub isolated_wallet: f64,
+    pub isolated_wallet: Amount,
     #[serde(rename = "ps")]
-    pub position_side: String,
+    pub position_side: PositionSide,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -470,7 +471,320 @@ pub struct StreamAggTrade {
     #[serde(rename = "m")]
     pub maker: bool,
     #[serde(rename = "p", with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub qty: Amount,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DepthOrderBookEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "U")]
+    pub first_update_id: u64,
+    #[serde(rename = "u")]
+    pub final_update_id: u64,
+    #[serde(rename = "pu")]
+    pub previous_final_update_id: u64,
+    #[serde(rename = "b")]
+    pub bids: Vec<Bids>,
+    #[serde(rename = "a")]
+    pub asks: Vec<Asks>,
+}
+
+/// The mark price and funding rate of a symbol.
+///
+/// Stream Name: \<symbol\>@markPrice or \<symbol\>@markPrice@1s, and !markPrice@arr for all
+/// symbols
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkPriceEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub mark_price: Amount,
+    #[serde(rename = "i", with = "string_or_float")]
+    pub index_price: Amount,
+    #[serde(rename = "P", with = "string_or_float")]
+    pub estimated_settle_price: Amount,
+    #[serde(rename = "r", with = "string_or_float")]
+    pub funding_rate: Amount,
+    #[serde(rename = "T")]
+    pub next_funding_time: u64,
+}
+
+/// Stream Name: \<symbol\>@kline_\<interval\>
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KlineEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "k")]
+    pub kline: StreamKline,
+}
+
+/// The klines of the contract of a pair.
+///
+/// Stream Name: \<pair\>_\<contractType\>@continuousKline_\<interval\>
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContinuousKlineEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(rename = "ct")]
+    pub contract_type: String,
+    #[serde(rename = "k")]
+    pub kline: StreamKline,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamKline {
+    #[serde(rename = "t")]
+    pub start_time: u64,
+    #[serde(rename = "T")]
+    pub end_time: u64,
+    #[serde(rename = "i")]
+    pub interval: String,
+    #[serde(rename = "f")]
+    pub first_trade_id: i64,
+    #[serde(rename = "L")]
+    pub last_trade_id: i64,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: Amount,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub close: Amount,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: Amount,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: Amount,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub volume: Amount,
+    #[serde(rename = "n")]
+    pub number_of_trades: u64,
+    #[serde(rename = "x")]
+    pub is_final_bar: bool,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quote_volume: Amount,
+    #[serde(rename = "V", with = "string_or_float")]
+    pub taker_buy_volume: Amount,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub taker_buy_quote_volume: Amount,
+}
+
+/// Stream Name: \<symbol\>@miniTicker, and !miniTicker@arr for all symbols
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MiniTickerEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub close: Amount,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: Amount,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: Amount,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: Amount,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub volume: Amount,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quote_volume: Amount,
+}
+
+/// Stream Name: \<symbol\>@ticker, and !ticker@arr for all symbols
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TickerEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price_change: Amount,
+    #[serde(rename = "P", with = "string_or_float")]
+    pub price_change_percent: Amount,
+    #[serde(rename = "w", with = "string_or_float")]
+    pub average_price: Amount,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub last_price: Amount,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub last_qty: Amount,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: Amount,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: Amount,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: Amount,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub volume: Amount,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quote_volume: Amount,
+    #[serde(rename = "O")]
+    pub open_time: u64,
+    #[serde(rename = "C")]
+    pub close_time: u64,
+    #[serde(rename = "F")]
+    pub first_trade_id: i64,
+    #[serde(rename = "L")]
+    pub last_trade_id: i64,
+    #[serde(rename = "n")]
+    pub num_trades: u64,
+}
+
+/// Stream Name: \<symbol\>@bookTicker, and !bookTicker for all symbols
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BookTickerEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub best_bid: Amount,
+    #[serde(rename = "B", with = "string_or_float")]
+    pub best_bid_qty: Amount,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub best_ask: Amount,
+    #[serde(rename = "A", with = "string_or_float")]
+    pub best_ask_qty: Amount,
+}
+
+/// A liquidation order.
+///
+/// Stream Name: \<symbol\>@forceOrder, and !forceOrder@arr for all symbols
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ForceOrderEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "o")]
+    pub order: ForceOrder,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ForceOrder {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "S")]
+    pub side: OrderSide,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
     #[serde(rename = "q", with = "string_or_float")]
-    pub qty: f64,
-}
\ No newline at end of file
+    pub original_quantity: Amount,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Amount,
+    #[serde(rename = "ap", with = "string_or_float")]
+    pub average_price: Amount,
+    #[serde(rename = "X")]
+    pub order_status: OrderStatus,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub last_filled_quantity: Amount,
+    #[serde(rename = "z", with = "string_or_float")]
+    pub filled_accumulated_quantity: Amount,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+}
+
+/// The price and composition of a composite index symbol.
+///
+/// Stream Name: \<symbol\>@compositeIndex
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CompositeIndexEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Amount,
+    #[serde(rename = "C")]
+    pub component: Option<String>,
+    #[serde(rename = "c")]
+    pub composition: Vec<IndexComponent>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IndexComponent {
+    #[serde(rename = "b")]
+    pub base_asset: String,
+    #[serde(rename = "q")]
+    pub quote_asset: String,
+    #[serde(rename = "w", with = "string_or_float")]
+    pub weight_in_quantity: Amount,
+    #[serde(rename = "W", with = "string_or_float")]
+    pub weight_in_percentage: Amount,
+    #[serde(rename = "i", with = "string_or_float")]
+    pub index_price: Amount,
+}
+
+/// A contract was listed, settled or delisted, or its notional brackets changed. The
+/// brackets are only sent when they change.
+///
+/// Stream Name: !contractInfo
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContractInfoEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(rename = "ct")]
+    pub contract_type: String,
+    #[serde(rename = "dt")]
+    pub delivery_date: u64,
+    #[serde(rename = "ot")]
+    pub onboard_date: u64,
+    #[serde(rename = "cs")]
+    pub status: String,
+    #[serde(default, rename = "bks")]
+    pub brackets: Vec<NotionalBracket>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotionalBracket {
+    #[serde(rename = "bs")]
+    pub bracket: u64,
+    #[serde(rename = "bnf")]
+    pub notional_floor: Amount,
+    #[serde(rename = "bnc")]
+    pub notional_cap: Amount,
+    #[serde(rename = "mmr")]
+    pub maint_margin_ratio: Amount,
+    #[serde(rename = "cf")]
+    pub cum: Amount,
+    #[serde(rename = "mi")]
+    pub min_leverage: u64,
+    #[serde(rename = "ma")]
+    pub max_leverage: u64,
+}
@@ -1,14 +1,19 @@
 use crate::errors::*;
 use crate::config::*;
 use crate::futures::model::*;
-use futures_util::StreamExt;
+use crate::websockets::{parse_response, send_request, unwrap_stream, Request, Subscriptions};
+pub use crate::streams::{ContractType, StreamName, UpdateSpeed};
+use futures_util::{ready, Stream, StreamExt};
 use url::Url;
-use serde_json::from_str;
+use serde_json::{from_str, from_value, Value};
 use serde::{Deserialize, Serialize};
 
 use std::{
+    collections::VecDeque,
     future::Future,
+    pin::Pin,
     sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll},
 };
 use tungstenite::Message;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
@@ -18,12 +23,30 @@ use tokio::net::TcpStream;
 static WEBSOCKET_URL: &str = "wss://fstream.binance.com/ws/";
 static WEBSOCKET_MULTI_STREAM: &str = "wss://fstream.binance.com/stream?streams="; // <streamName1>/<streamName2>/<streamName3>
 
-static ORDER_TRADE_UPDATE: &str = "ORDER_TRADE_UPDATE";
-static ACCOUNT_UPDATE: &str = "ACCOUNT_UPDATE";
-static ACCOUNT_CONFIG_UPDATE: &str = "ACCOUNT_CONFIG_UPDATE";
-static LISTEN_KEY_EXPIRED: &str = "listenKeyExpired";
-static AGGREGATED_TRADE: &str = "aggTrade";
-static STREAM: &str = "stream";
+const ORDER_TRADE_UPDATE: &str = "ORDER_TRADE_UPDATE";
+const ACCOUNT_UPDATE: &str = "ACCOUNT_UPDATE";
+const ACCOUNT_CONFIG_UPDATE: &str = "ACCOUNT_CONFIG_UPDATE";
+const LISTEN_KEY_EXPIRED: &str = "listenKeyExpired";
+const AGGREGATED_TRADE: &str = "aggTrade";
+const DEPTH_ORDERBOOK: &str = "depthUpdate";
+const MARK_PRICE: &str = "markPriceUpdate";
+const KLINE: &str = "kline";
+const CONTINUOUS_KLINE: &str = "continuous_kline";
+const MINI_TICKER: &str = "24hrMiniTicker";
+const TICKER: &str = "24hrTicker";
+const BOOK_TICKER: &str = "bookTicker";
+const FORCE_ORDER: &str = "forceOrder";
+const COMPOSITE_INDEX: &str = "compositeIndex";
+const CONTRACT_INFO: &str = "contractInfo";
+
+// `Config::futures_ws_endpoint` is the host, raw streams are under `/ws/` on it
+fn config_url(config: &Config, subscription: &str) -> String {
+    format!(
+        "{}/ws/{}",
+        config.futures_ws_endpoint.trim_end_matches('/'),
+        subscription
+    )
+}
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -32,7 +55,28 @@ pub enum FuturesWebsocketEvent {
     AccountUpdate(AccountUpdateEvent),
     LeverageUpdate(LeverageUpdateEvent),
     ListenKeyExpired(ListenKeyExpiredEvent),
-    AggTrade(StreamAggTrade)
+    AggTrade(StreamAggTrade),
+    /// A partial or diff depth update.
+    DepthOrderBook(DepthOrderBookEvent),
+    MarkPrice(MarkPriceEvent),
+    AllMarkPrices(Vec<MarkPriceEvent>),
+    Kline(KlineEvent),
+    ContinuousKline(ContinuousKlineEvent),
+    MiniTicker(MiniTickerEvent),
+    AllMiniTickers(Vec<MiniTickerEvent>),
+    Ticker(TickerEvent),
+    AllTickers(Vec<TickerEvent>),
+    BookTicker(BookTickerEvent),
+    /// A liquidation order.
+    ForceOrder(ForceOrderEvent),
+    CompositeIndex(CompositeIndexEvent),
+    ContractInfo(ContractInfoEvent),
+    /// An event of a type this crate doesn't decode.
+    Unknown(Value),
+    /// The connection was lost and a `ReconnectPolicy` is reconnecting, with the reason.
+    Disconnected(String),
+    /// The connection was re-established with the same streams.
+    Reconnected,
 }
 
 // Account
@@ -46,6 +90,9 @@ where
     handler: Box<dyn Fn(FuturesWebsocketEvent, S) -> Fut + 'static + Send + Sync>,
     subscription: &'a str,
     state: S,
+    url: String,
+    reconnect_policy: Option<ReconnectPolicy>,
+    subscriptions: Subscriptions,
 }
 
 impl<'a, F, S> FuturesWebSockets<'a, F, S>
@@ -62,56 +109,48 @@ where
             handler: Box::new(handler),
             subscription: "",
             state,
+            url: String::new(),
+            reconnect_policy: None,
+            subscriptions: Subscriptions::default(),
         }
     }
 
+    /// Reconnect from `event_loop` when the connection is closed or fails, instead of
+    /// returning an error.
+    pub fn set_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = Some(reconnect_policy);
+        self
+    }
+
     pub async fn connect(&mut self, subscription: &'a str) -> Result<()> {
         self.subscription = subscription;
+        self.subscriptions = Subscriptions::new(&[subscription])?;
         let wss: String = format!("{}{}", WEBSOCKET_URL, subscription);
-        let url = Url::parse(&wss)?;
-
-        match connect_async(url).await {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => {
-                bail!(format!("Error during handshake {}", e));
-            }
-        }
+        self.connect_url(wss).await
     }
 
     pub async fn connect_multiple_streams<Str: AsRef<str>>(&mut self, streams: &[Str]) -> Result<()> {
+        self.subscriptions = Subscriptions::new(streams)?;
         let wss: String = format!("{}{}", WEBSOCKET_MULTI_STREAM, streams.iter().map(|s| s.as_ref()).collect::<Vec<_>>().join("/"));
-        let url = Url::parse(&wss)?;
+        self.connect_url(wss).await
+    }
 
-        match connect_async(url).await {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => {
-                bail!(format!("Error during handshake {}", e));
-            }
-        }
+    pub async fn connect_streams(&mut self, streams: &[StreamName]) -> Result<()> {
+        self.connect_multiple_streams(&futures_names(streams)?).await
     }
 
     pub async fn connect_with_config(
         &mut self, subscription: &'a str, config: &'a Config,
     ) -> Result<()> {
         self.subscription = subscription;
-        let wss: String = format!("{}{}", &config.ws_endpoint, subscription);
-        let url = Url::parse(&wss)?;
+        self.subscriptions = Subscriptions::new(&[subscription])?;
+        self.connect_url(config_url(config, subscription)).await
+    }
 
-        match connect_async(url).await {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => {
-                bail!(format!("Error during handshake {}", e));
-            }
-        }
+    async fn connect_url(&mut self, wss: String) -> Result<()> {
+        self.socket = Some(connect_socket(&wss).await?);
+        self.url = wss;
+        Ok(())
     }
 
     pub async fn disconnect(&mut self) -> Result<()> {
@@ -122,67 +161,120 @@ where
         }
     }
 
-    async fn handle_msg(&mut self, msg: &str) -> Result<()> {
-        let value: serde_json::Value = serde_json::from_str(msg)?;
-        let msg = if msg.find(STREAM) != None {
-            if value["data"] != serde_json::Value::Null {
-                format!("{}", value["data"])
-            } else {
-                msg.to_owned()
+    /// Start listening to `streams` on the open connection.
+    pub async fn subscribe<Str: AsRef<str>>(&mut self, streams: &[Str]) -> Result<()> {
+        let request = self.subscriptions.subscribe(streams)?;
+        self.request(request).await?;
+        Ok(())
+    }
+
+    pub async fn unsubscribe<Str: AsRef<str>>(&mut self, streams: &[Str]) -> Result<()> {
+        let request = self.subscriptions.unsubscribe(streams);
+        self.request(request).await?;
+        Ok(())
+    }
+
+    /// The streams Binance reports the connection listening to.
+    pub async fn list_subscriptions(&mut self) -> Result<Vec<String>> {
+        let request = self.subscriptions.list_subscriptions();
+        Ok(serde_json::from_value(self.request(request).await?)?)
+    }
+
+    /// Wrap the events in `{"stream": ..., "data": ...}`, as combined streams do.
+    pub async fn set_combined(&mut self, combined: bool) -> Result<()> {
+        let request = self.subscriptions.set_combined(combined);
+        self.request(request).await?;
+        Ok(())
+    }
+
+    // Events received while waiting for the response are handled after it
+    async fn request(&mut self, request: Request) -> Result<serde_json::Value> {
+        tokio::time::sleep(self.subscriptions.delay()).await;
+        let mut received = Vec::new();
+        let result = match self.socket {
+            Some(ref mut socket) => {
+                send_request(
+                    &mut socket.0,
+                    &mut self.subscriptions,
+                    &request,
+                    &mut received,
+                )
+                .await
             }
-        } else {
-            msg.to_owned()
+            None => Err("Not connected".into()),
         };
-        
-        if msg.find(ORDER_TRADE_UPDATE) != None {
-            let order_trade: OrderTradeUpdateEvent = from_str(&msg)?;
-            (self.handler)(
-                FuturesWebsocketEvent::OrderTrade(order_trade),
-                self.state.clone(),
-            )
-            .await?;
-        } else if msg.find(ACCOUNT_UPDATE) != None {
-            let account_update: AccountUpdateEvent = from_str(&msg)?;
-            (self.handler)(
-                FuturesWebsocketEvent::AccountUpdate(account_update),
-                self.state.clone(),
-            )
-            .await?;
-        } else if msg.find(ACCOUNT_CONFIG_UPDATE) != None {
-            let leverage_update: LeverageUpdateEvent = from_str(&msg)?;
-            (self.handler)(
-                FuturesWebsocketEvent::LeverageUpdate(leverage_update),
-                self.state.clone(),
-            )
-            .await?;
-        } else if msg.find(LISTEN_KEY_EXPIRED) != None {
-            let listen_key_expired: ListenKeyExpiredEvent = from_str(&msg)?;
-            (self.handler)(
-                FuturesWebsocketEvent::ListenKeyExpired(listen_key_expired),
-                self.state.clone(),
-            )
-            .await?;
-        } else if msg.find(AGGREGATED_TRADE) != None {
-            let stream_agg_trade: StreamAggTrade = from_str(&msg)?;
-            (self.handler)(
-                FuturesWebsocketEvent::AggTrade(stream_agg_trade),
-                self.state.clone(),
-            )
-            .await?;
-        } else {
-            bail!(format!("Can't decode: {:?}", &msg));
+        for msg in received {
+            if let Err(e) = self.handle_msg(&msg).await {
+                bail!(format!("Error on handling stream message: {}", e));
+            }
+        }
+        result
+    }
+
+    // Subscriptions made since connecting are lost with the connection
+    async fn restore_subscriptions(&mut self) -> Result<()> {
+        let (subscribe, unsubscribe) = self.subscriptions.changes();
+        if !subscribe.is_empty() {
+            self.subscribe(&subscribe).await?;
+        }
+        if !unsubscribe.is_empty() {
+            self.unsubscribe(&unsubscribe).await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_msg(&mut self, msg: &str) -> Result<()> {
+        if let Some(event) = parse_event(msg)? {
+            (self.handler)(event, self.state.clone()).await?;
         }
         Ok(())
     }
 
+    // Returns `error` when there is no reconnect policy or it gives up
+    async fn reconnect(&mut self, running: &AtomicBool, error: Error) -> Result<()> {
+        let reconnect_policy = match self.reconnect_policy.clone() {
+            Some(reconnect_policy) => reconnect_policy,
+            None => return Err(error),
+        };
+        self.socket = None;
+        let disconnected = FuturesWebsocketEvent::Disconnected(error.to_string());
+        (self.handler)(disconnected, self.state.clone()).await?;
+
+        let mut attempt = 1;
+        loop {
+            tokio::time::sleep(reconnect_policy.backoff(attempt)).await;
+            if !running.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let reconnected = match self.connect_url(self.url.clone()).await {
+                Ok(()) => self.restore_subscriptions().await,
+                Err(e) => Err(e),
+            };
+            match reconnected {
+                Ok(()) => {
+                    return (self.handler)(FuturesWebsocketEvent::Reconnected, self.state.clone())
+                        .await
+                }
+                Err(e) if !reconnect_policy.can_retry(attempt) => return Err(e),
+                Err(_) => attempt += 1,
+            }
+        }
+    }
+
     pub async fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
         while running.load(Ordering::Relaxed) {
             if let Some(ref mut socket) = self.socket {
-                let message = if let Some(message) = socket.0.next().await {
-                    message
-                } else {
-                    continue;
-                }?;
+                let message = match socket.0.next().await {
+                    Some(Ok(message)) => message,
+                    Some(Err(e)) => {
+                        self.reconnect(running, e.into()).await?;
+                        continue;
+                    }
+                    None => {
+                        self.reconnect(running, "Disconnected".into()).await?;
+                        continue;
+                    }
+                };
                 match message {
                     Message::Text(msg) => match self.handle_msg(&msg).await {
                         Ok(_) => {}
@@ -195,7 +287,8 @@ where
                     },
                     Message::Ping(_) | Message::Pong(_) | Message::Binary(_) => (),
                     Message::Close(e) => {
-                        bail!(format!("Disconnected {:?}", e));
+                        let error = format!("Disconnected {:?}", e).into();
+                        self.reconnect(running, error).await?;
                     }
                 }
             }
@@ -203,3 +296,200 @@ where
         Ok(())
     }
 }
+
+// Decodes a message of a single or combined stream, None for responses to requests
+fn parse_event(msg: &str) -> Result<Option<FuturesWebsocketEvent>> {
+    let value: Value = from_str(msg)?;
+    if parse_response(&value).is_some() {
+        return Ok(None);
+    }
+    let (value, _) = unwrap_stream(value);
+    Ok(Some(decode_event(value)?))
+}
+
+// Dispatches on the `e` field, of the first event for the streams of all symbols
+fn decode_event(value: Value) -> Result<FuturesWebsocketEvent> {
+    let event_type = match &value {
+        Value::Array(events) => events.first().and_then(|event| event["e"].as_str()),
+        event => event["e"].as_str(),
+    };
+    let all = value.is_array();
+    let event = match event_type.map(str::to_owned).as_deref() {
+        Some(ORDER_TRADE_UPDATE) => FuturesWebsocketEvent::OrderTrade(from_value(value)?),
+        Some(ACCOUNT_UPDATE) => FuturesWebsocketEvent::AccountUpdate(from_value(value)?),
+        Some(ACCOUNT_CONFIG_UPDATE) => FuturesWebsocketEvent::LeverageUpdate(from_value(value)?),
+        Some(LISTEN_KEY_EXPIRED) => FuturesWebsocketEvent::ListenKeyExpired(from_value(value)?),
+        Some(AGGREGATED_TRADE) => FuturesWebsocketEvent::AggTrade(from_value(value)?),
+        Some(DEPTH_ORDERBOOK) => FuturesWebsocketEvent::DepthOrderBook(from_value(value)?),
+        Some(MARK_PRICE) if all => FuturesWebsocketEvent::AllMarkPrices(from_value(value)?),
+        Some(MARK_PRICE) => FuturesWebsocketEvent::MarkPrice(from_value(value)?),
+        Some(KLINE) => FuturesWebsocketEvent::Kline(from_value(value)?),
+        Some(CONTINUOUS_KLINE) => FuturesWebsocketEvent::ContinuousKline(from_value(value)?),
+        Some(MINI_TICKER) if all => FuturesWebsocketEvent::AllMiniTickers(from_value(value)?),
+        Some(MINI_TICKER) => FuturesWebsocketEvent::MiniTicker(from_value(value)?),
+        Some(TICKER) if all => FuturesWebsocketEvent::AllTickers(from_value(value)?),
+        Some(TICKER) => FuturesWebsocketEvent::Ticker(from_value(value)?),
+        Some(BOOK_TICKER) => FuturesWebsocketEvent::BookTicker(from_value(value)?),
+        Some(FORCE_ORDER) => FuturesWebsocketEvent::ForceOrder(from_value(value)?),
+        Some(COMPOSITE_INDEX) => FuturesWebsocketEvent::CompositeIndex(from_value(value)?),
+        Some(CONTRACT_INFO) => FuturesWebsocketEvent::ContractInfo(from_value(value)?),
+        _ => FuturesWebsocketEvent::Unknown(value),
+    };
+    Ok(event)
+}
+
+/// A connection yielding the events of its streams, as an alternative to the
+/// `FuturesWebSockets` callback and `event_loop`.
+///
+/// A message that can't be decoded yields an error and the stream carries on. It ends
+/// after the connection is closed or fails. Dropping it closes the connection.
+pub struct FuturesEventStream {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    subscriptions: Subscriptions,
+    // Messages received while waiting for a response
+    received: VecDeque<String>,
+    closed: bool,
+}
+
+impl FuturesEventStream {
+    pub async fn connect(subscription: &str) -> Result<FuturesEventStream> {
+        let wss: String = format!("{}{}", WEBSOCKET_URL, subscription);
+        FuturesEventStream::connect_url(&wss, &[subscription]).await
+    }
+
+    pub async fn connect_multiple_streams<Str: AsRef<str>>(
+        streams: &[Str],
+    ) -> Result<FuturesEventStream> {
+        let names: Vec<&str> = streams.iter().map(|s| s.as_ref()).collect();
+        let wss: String = format!("{}{}", WEBSOCKET_MULTI_STREAM, names.join("/"));
+        FuturesEventStream::connect_url(&wss, streams).await
+    }
+
+    pub async fn connect_streams(streams: &[StreamName]) -> Result<FuturesEventStream> {
+        FuturesEventStream::connect_multiple_streams(&futures_names(streams)?).await
+    }
+
+    pub async fn connect_with_config(
+        subscription: &str, config: &Config,
+    ) -> Result<FuturesEventStream> {
+        FuturesEventStream::connect_url(&config_url(config, subscription), &[subscription]).await
+    }
+
+    async fn connect_url<Str: AsRef<str>>(
+        wss: &str, streams: &[Str],
+    ) -> Result<FuturesEventStream> {
+        let subscriptions = Subscriptions::new(streams)?;
+        let (socket, _) = connect_socket(wss).await?;
+        Ok(FuturesEventStream {
+            socket,
+            subscriptions,
+            received: VecDeque::new(),
+            closed: false,
+        })
+    }
+
+    /// Start listening to `streams` on the open connection.
+    pub async fn subscribe<Str: AsRef<str>>(&mut self, streams: &[Str]) -> Result<()> {
+        let request = self.subscriptions.subscribe(streams)?;
+        self.request(request).await?;
+        Ok(())
+    }
+
+    pub async fn unsubscribe<Str: AsRef<str>>(&mut self, streams: &[Str]) -> Result<()> {
+        let request = self.subscriptions.unsubscribe(streams);
+        self.request(request).await?;
+        Ok(())
+    }
+
+    /// The streams Binance reports the connection listening to.
+    pub async fn list_subscriptions(&mut self) -> Result<Vec<String>> {
+        let request = self.subscriptions.list_subscriptions();
+        Ok(serde_json::from_value(self.request(request).await?)?)
+    }
+
+    /// Wrap the events in `{"stream": ..., "data": ...}`, as combined streams do.
+    pub async fn set_combined(&mut self, combined: bool) -> Result<()> {
+        let request = self.subscriptions.set_combined(combined);
+        self.request(request).await?;
+        Ok(())
+    }
+
+    // Events received while waiting for the response are yielded after it
+    async fn request(&mut self, request: Request) -> Result<serde_json::Value> {
+        tokio::time::sleep(self.subscriptions.delay()).await;
+        let mut received = Vec::new();
+        let result = send_request(
+            &mut self.socket,
+            &mut self.subscriptions,
+            &request,
+            &mut received,
+        )
+        .await;
+        self.received.extend(received);
+        result
+    }
+
+    /// Close the connection with a close frame rather than by dropping it.
+    pub async fn close(&mut self) -> Result<()> {
+        self.closed = true;
+        Ok(self.socket.close(None).await?)
+    }
+
+    // Whether the connection was closed or failed, after which the stream ends
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+impl Stream for FuturesEventStream {
+    type Item = Result<FuturesWebsocketEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        while let Some(msg) = this.received.pop_front() {
+            if let Some(event) = parse_event(&msg).transpose() {
+                return Poll::Ready(Some(event));
+            }
+        }
+        while !this.closed {
+            let message = match ready!(this.socket.poll_next_unpin(cx)) {
+                Some(Ok(message)) => message,
+                Some(Err(e)) => {
+                    this.closed = true;
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+                None => break,
+            };
+            match message {
+                Message::Text(msg) => {
+                    if let Some(event) = parse_event(&msg).transpose() {
+                        return Poll::Ready(Some(event));
+                    }
+                }
+                Message::Ping(_) | Message::Pong(_) | Message::Binary(_) => (),
+                Message::Close(e) => {
+                    this.closed = true;
+                    return Poll::Ready(Some(Err(format!("Disconnected {:?}", e).into())));
+                }
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+fn futures_names(streams: &[StreamName]) -> Result<Vec<String>> {
+    streams.iter().map(StreamName::futures_name).collect()
+}
+
+async fn connect_socket(
+    wss: &str,
+) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> {
+    let url = Url::parse(wss)?;
+
+    match connect_async(url).await {
+        Ok(answer) => Ok(answer),
+        Err(e) => {
+            bail!(format!("Error during handshake {}", e));
+        }
+    }
+}
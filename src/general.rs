@@ -9,50 +9,10 @@ pub struct General {
     pub client: Client,
 }
 
-#[cfg(feature = "blocking")]
-impl General {
-    // Test connectivity
-    pub fn ping(&self) -> Result<String> {
-        self.client.get(API::Spot(Spot::Ping), None)?;
-        Ok("pong".into())
-    }
-
-    // Check server time
-    pub fn get_server_time(&self) -> Result<ServerTime> {
-        self.client.get(API::Spot(Spot::Time), None)
-    }
-
-    // Obtain exchange information
-    // - Current exchange trading rules and symbol information
-    pub fn exchange_info(&self) -> Result<ExchangeInformation> {
-        self.client.get(API::Spot(Spot::ExchangeInfo), None)
-    }
-
-    // Get Symbol information
-    pub fn get_symbol_info<S>(&self, symbol: S) -> Result<Symbol>
-    where
-        S: Into<String>,
-    {
-        let upper_symbol = symbol.into().to_uppercase();
-        match self.exchange_info() {
-            Ok(info) => {
-                for item in info.symbols {
-                    if item.symbol == upper_symbol {
-                        return Ok(item);
-                    }
-                }
-                bail!("Symbol not found")
-            }
-            Err(e) => Err(e),
-        }
-    }
-}
-
-#[cfg(not(feature = "blocking"))]
 impl General {
     // Test connectivity
     pub async fn ping(&self) -> Result<String> {
-        self.client.get(API::Spot(Spot::Ping), None).await?;
+        self.client.get::<serde_json::Value>(API::Spot(Spot::Ping), None).await?;
         Ok("pong".into())
     }
 
@@ -63,8 +23,13 @@ impl General {
 
     // Obtain exchange information
     // - Current exchange trading rules and symbol information
+    // Also seeds the client's rate limits
     pub async fn exchange_info(&self) -> Result<ExchangeInformation> {
-        self.client.get(API::Spot(Spot::ExchangeInfo), None).await
+        let info: ExchangeInformation =
+            self.client.get(API::Spot(Spot::ExchangeInfo), None).await?;
+        self.client.rate_limits().seed(&info.rate_limits);
+
+        Ok(info)
     }
 
     // Get Symbol information
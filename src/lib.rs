@@ -5,6 +5,9 @@
     unused_imports,
     unused_import_braces
 )]
+// `Error` is as large as its largest foreign error, `tungstenite::Error`, and is returned
+// by value throughout the crate, so boxing the Binance error payloads would not shrink it
+#![allow(clippy::result_large_err)]
 
 #[macro_use]
 extern crate error_chain;
@@ -20,7 +23,19 @@ pub mod api;
 pub mod config;
 pub mod general;
 pub mod market;
+pub mod order_book;
+pub mod pagination;
+pub mod rate_limit;
+pub mod signer;
+pub mod streams;
+pub mod time_sync;
 pub mod userstream;
 pub mod websockets;
 
 pub mod futures;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+#[cfg(feature = "decimal")]
+pub use rust_decimal;
@@ -3,10 +3,14 @@ use crate::model::*;
 use crate::client::*;
 use crate::errors::*;
 use std::collections::BTreeMap;
-use serde_json::Value;
+use crate::order_book::{DepthUpdate, LocalOrderBook, UpdateStatus, SNAPSHOT_DEPTH};
+use crate::pagination::{pages, Cursor, Pager, KLINES_LIMIT, TRADES_LIMIT};
+use futures_util::stream::Stream;
 use crate::api::API;
 use crate::api::Spot;
 
+pub use crate::model::KlineInterval;
+
 #[derive(Clone)]
 pub struct Market {
     pub client: Client,
@@ -14,10 +18,9 @@ pub struct Market {
 }
 
 // Market Data endpoints
-#[cfg(feature = "blocking")]
 impl Market {
     // Order book at the default depth of 100
-    pub fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
+    pub async fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
     where
         S: Into<String>,
     {
@@ -26,12 +29,12 @@ impl Market {
         parameters.insert("symbol".into(), symbol.into());
         let request = build_request(&parameters);
 
-        self.client.get(API::Spot(Spot::Depth), Some(request))
+        self.client.get(API::Spot(Spot::Depth), Some(request)).await
     }
 
     // Order book at a custom depth. Currently supported values
     // are 5, 10, 20, 50, 100, 500, 1000 and 5000
-    pub fn get_custom_depth<S>(&self, symbol: S, depth: u64) -> Result<OrderBook>
+    pub async fn get_custom_depth<S>(&self, symbol: S, depth: u64) -> Result<OrderBook>
     where
         S: Into<String>,
     {
@@ -41,16 +44,33 @@ impl Market {
         parameters.insert("limit".into(), depth.to_string());
         let request = build_request(&parameters);
 
-        self.client.get(API::Spot(Spot::Depth), Some(request))
+        self.client.get(API::Spot(Spot::Depth), Some(request)).await
+    }
+
+    /// Apply a depth update to `book`, fetching a snapshot when the book needs one:
+    /// before the first update is applied and after a missed update.
+    pub async fn update_order_book<U>(
+        &self, book: &mut LocalOrderBook, update: U,
+    ) -> Result<UpdateStatus>
+    where
+        U: Into<DepthUpdate>,
+    {
+        let status = book.push(update);
+        if book.needs_snapshot() {
+            let snapshot = self.get_custom_depth(book.symbol(), SNAPSHOT_DEPTH).await?;
+            book.apply_snapshot(snapshot);
+        }
+
+        Ok(status)
     }
 
     // Latest price for ALL symbols.
-    pub fn get_all_prices(&self) -> Result<Prices> {
-        self.client.get(API::Spot(Spot::Price), None)
+    pub async fn get_all_prices(&self) -> Result<Prices> {
+        self.client.get(API::Spot(Spot::Price), None).await
     }
 
     // Latest price for ONE symbol.
-    pub fn get_price<S>(&self, symbol: S) -> Result<SymbolPrice>
+    pub async fn get_price<S>(&self, symbol: S) -> Result<SymbolPrice>
     where
         S: Into<String>,
     {
@@ -59,11 +79,11 @@ impl Market {
         parameters.insert("symbol".into(), symbol.into());
         let request = build_request(&parameters);
 
-        self.client.get(API::Spot(Spot::Price), Some(request))
+        self.client.get(API::Spot(Spot::Price), Some(request)).await
     }
 
     // Average price for ONE symbol.
-    pub fn get_average_price<S>(&self, symbol: S) -> Result<AveragePrice>
+    pub async fn get_average_price<S>(&self, symbol: S) -> Result<AveragePrice>
     where
         S: Into<String>,
     {
@@ -72,17 +92,19 @@ impl Market {
         parameters.insert("symbol".into(), symbol.into());
         let request = build_request(&parameters);
 
-        self.client.get(API::Spot(Spot::AvgPrice), Some(request))
+        self.client
+            .get(API::Spot(Spot::AvgPrice), Some(request))
+            .await
     }
 
     // Symbols order book ticker
     // -> Best price/qty on the order book for ALL symbols.
-    pub fn get_all_book_tickers(&self) -> Result<BookTickers> {
-        self.client.get(API::Spot(Spot::BookTicker), None)
+    pub async fn get_all_book_tickers(&self) -> Result<BookTickers> {
+        self.client.get(API::Spot(Spot::BookTicker), None).await
     }
 
     // -> Best price/qty on the order book for ONE symbol
-    pub fn get_book_ticker<S>(&self, symbol: S) -> Result<Tickers>
+    pub async fn get_book_ticker<S>(&self, symbol: S) -> Result<Tickers>
     where
         S: Into<String>,
     {
@@ -91,11 +113,13 @@ impl Market {
         parameters.insert("symbol".into(), symbol.into());
         let request = build_request(&parameters);
 
-        self.client.get(API::Spot(Spot::BookTicker), Some(request))
+        self.client
+            .get(API::Spot(Spot::BookTicker), Some(request))
+            .await
     }
 
     // 24hr ticker price change statistics
-    pub fn get_24h_price_stats<S>(&self, symbol: S) -> Result<PriceStats>
+    pub async fn get_24h_price_stats<S>(&self, symbol: S) -> Result<PriceStats>
     where
         S: Into<String>,
     {
@@ -104,182 +128,107 @@ impl Market {
         parameters.insert("symbol".into(), symbol.into());
         let request = build_request(&parameters);
 
-        self.client.get(API::Spot(Spot::Ticker24hr), Some(request))
+        self.client
+            .get(API::Spot(Spot::Ticker24hr), Some(request))
+            .await
     }
 
     // 24hr ticker price change statistics for all symbols
-    pub fn get_all_24h_price_stats(&self) -> Result<Vec<PriceStats>> {
-        self.client.get(API::Spot(Spot::Ticker24hr), None)
+    pub async fn get_all_24h_price_stats(&self) -> Result<Vec<PriceStats>> {
+        self.client.get(API::Spot(Spot::Ticker24hr), None).await
     }
 
-    // Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
-    // https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#klinecandlestick-data
-    pub fn get_klines<S1, S2, S3, S4, S5>(
-        &self, symbol: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
-    ) -> Result<KlineSummaries>
+    // Recent trades, newest last
+    pub async fn get_trades<S1, S2>(&self, symbol: S1, limit: S2) -> Result<Trades>
     where
         S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<Option<u16>>,
-        S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
+        S2: Into<Option<u16>>,
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
 
         parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("interval".into(), interval.into());
-
-        // Add three optional parameters
         if let Some(lt) = limit.into() {
             parameters.insert("limit".into(), format!("{}", lt));
         }
-        if let Some(st) = start_time.into() {
-            parameters.insert("startTime".into(), format!("{}", st));
-        }
-        if let Some(et) = end_time.into() {
-            parameters.insert("endTime".into(), format!("{}", et));
-        }
-
-        let request = build_request(&parameters);
-        let data: Vec<Vec<Value>> = self.client.get(API::Spot(Spot::Klines), Some(request))?;
-
-        let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_f64(&row[1]),
-                    high: to_f64(&row[2]),
-                    low: to_f64(&row[3]),
-                    close: to_f64(&row[4]),
-                    volume: to_f64(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_f64(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_f64(&row[9]),
-                    taker_buy_quote_asset_volume: to_f64(&row[10]),
-                })
-                .collect(),
-        );
-        Ok(klines)
-    }
-}
-
-// Market Data endpoints
-#[cfg(not(feature = "blocking"))]
-impl Market {
-    // Order book at the default depth of 100
-    pub async fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-
-        parameters.insert("symbol".into(), symbol.into());
-        let request = build_request(&parameters);
-
-        self.client.get(API::Spot(Spot::Depth), Some(request)).await
-    }
-
-    // Order book at a custom depth. Currently supported values
-    // are 5, 10, 20, 50, 100, 500, 1000 and 5000
-    pub async fn get_custom_depth<S>(&self, symbol: S, depth: u64) -> Result<OrderBook>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
 
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("limit".into(), depth.to_string());
         let request = build_request(&parameters);
-
-        self.client.get(API::Spot(Spot::Depth), Some(request)).await
-    }
-
-    // Latest price for ALL symbols.
-    pub async fn get_all_prices(&self) -> Result<Prices> {
-        self.client.get(API::Spot(Spot::Price), None).await
+        self.client
+            .get(API::Spot(Spot::Trades), Some(request))
+            .await
     }
 
-    // Latest price for ONE symbol.
-    pub async fn get_price<S>(&self, symbol: S) -> Result<SymbolPrice>
+    // Older trades, starting at trade id 'from_id' or the most recent ones without it
+    pub async fn get_historical_trades<S1, S2, S3>(
+        &self, symbol: S1, from_id: S2, limit: S3,
+    ) -> Result<Trades>
     where
-        S: Into<String>,
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u16>>,
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
 
         parameters.insert("symbol".into(), symbol.into());
-        let request = build_request(&parameters);
-
-        self.client.get(API::Spot(Spot::Price), Some(request)).await
-    }
 
-    // Average price for ONE symbol.
-    pub async fn get_average_price<S>(&self, symbol: S) -> Result<AveragePrice>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        // Add two optional parameters
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(fi) = from_id.into() {
+            parameters.insert("fromId".into(), format!("{}", fi));
+        }
 
-        parameters.insert("symbol".into(), symbol.into());
         let request = build_request(&parameters);
-
         self.client
-            .get(API::Spot(Spot::AvgPrice), Some(request))
+            .get(API::Spot(Spot::HistoricalTrades), Some(request))
             .await
     }
 
-    // Symbols order book ticker
-    // -> Best price/qty on the order book for ALL symbols.
-    pub async fn get_all_book_tickers(&self) -> Result<BookTickers> {
-        self.client.get(API::Spot(Spot::BookTicker), None).await
-    }
-
-    // -> Best price/qty on the order book for ONE symbol
-    pub async fn get_book_ticker<S>(&self, symbol: S) -> Result<Tickers>
+    // Compressed trades: fills of one taker order at the same price are aggregated
+    pub async fn get_agg_trades<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, from_id: S2, start_time: S3, end_time: S4, limit: S5,
+    ) -> Result<AggTrades>
     where
-        S: Into<String>,
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u16>>,
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
 
         parameters.insert("symbol".into(), symbol.into());
-        let request = build_request(&parameters);
-
-        self.client
-            .get(API::Spot(Spot::BookTicker), Some(request))
-            .await
-    }
 
-    // 24hr ticker price change statistics
-    pub async fn get_24h_price_stats<S>(&self, symbol: S) -> Result<PriceStats>
-    where
-        S: Into<String>,
-    {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        // Add four optional parameters
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+        if let Some(fi) = from_id.into() {
+            parameters.insert("fromId".into(), format!("{}", fi));
+        }
 
-        parameters.insert("symbol".into(), symbol.into());
         let request = build_request(&parameters);
-
         self.client
-            .get(API::Spot(Spot::Ticker24hr), Some(request))
+            .get(API::Spot(Spot::AggTrades), Some(request))
             .await
     }
 
-    // 24hr ticker price change statistics for all symbols
-    pub async fn get_all_24h_price_stats(&self) -> Result<Vec<PriceStats>> {
-        self.client.get(API::Spot(Spot::Ticker24hr), None).await
-    }
-
-    // Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
+    // Returns up to 'limit' klines for given symbol and interval
     // https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#klinecandlestick-data
-    pub async fn get_klines<S1, S2, S3, S4, S5>(
-        &self, symbol: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
+    pub async fn get_klines<S1, S2, S3, S4>(
+        &self, symbol: S1, interval: KlineInterval, limit: S2, start_time: S3, end_time: S4,
     ) -> Result<KlineSummaries>
     where
         S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<Option<u16>>,
+        S2: Into<Option<u16>>,
+        S3: Into<Option<u64>>,
         S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
 
@@ -298,28 +247,93 @@ impl Market {
         }
 
         let request = build_request(&parameters);
-        let data: Vec<Vec<Value>> = self
+        let klines: Vec<KlineSummary> = self
             .client
             .get(API::Spot(Spot::Klines), Some(request))
             .await?;
 
-        let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_f64(&row[1]),
-                    high: to_f64(&row[2]),
-                    low: to_f64(&row[3]),
-                    close: to_f64(&row[4]),
-                    volume: to_f64(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_f64(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_f64(&row[9]),
-                    taker_buy_quote_asset_volume: to_f64(&row[10]),
-                })
-                .collect(),
-        );
-        Ok(klines)
+        Ok(KlineSummaries::AllKlineSummaries(klines))
+    }
+
+    /// All klines opened from `start_time` to `end_time`, or until now without it, as
+    /// a `Stream` fetching 1000 at a time.
+    pub fn get_klines_range<S1, S2>(
+        &self, symbol: S1, interval: KlineInterval, start_time: u64, end_time: S2,
+    ) -> impl Stream<Item = Result<KlineSummary>> + '_
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+    {
+        let symbol: String = symbol.into();
+        let end_time = end_time.into();
+        let pager = Pager::new(Cursor::StartTime(start_time), end_time, KLINES_LIMIT);
+
+        pages(pager, move |cursor| {
+            let symbol = symbol.clone();
+            async move {
+                let limit = format!("limit={}", KLINES_LIMIT);
+                self.client
+                    .wait_for_weight(&API::Spot(Spot::Klines), &limit)
+                    .await?;
+                let KlineSummaries::AllKlineSummaries(klines) = self
+                    .get_klines(symbol, interval, KLINES_LIMIT, cursor.time(), end_time)
+                    .await?;
+                Ok(klines)
+            }
+        })
+    }
+
+    /// All aggregate trades from `start_time` to `end_time`, or until now without it,
+    /// as a `Stream` fetching 1000 at a time.
+    pub fn get_agg_trades_range<S1, S2>(
+        &self, symbol: S1, start_time: u64, end_time: S2,
+    ) -> impl Stream<Item = Result<AggTrade>> + '_
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+    {
+        let symbol: String = symbol.into();
+        let pager = Pager::new(Cursor::StartTime(start_time), end_time.into(), TRADES_LIMIT);
+
+        pages(pager, move |cursor| {
+            let symbol = symbol.clone();
+            async move {
+                let limit = format!("limit={}", TRADES_LIMIT);
+                self.client
+                    .wait_for_weight(&API::Spot(Spot::AggTrades), &limit)
+                    .await?;
+                let AggTrades::AllAggTrades(trades) = self
+                    .get_agg_trades(symbol, cursor.id(), cursor.time(), None, TRADES_LIMIT)
+                    .await?;
+                Ok(trades)
+            }
+        })
+    }
+
+    /// All trades from id `from_id` to `to_id`, or until the latest one without it, as
+    /// a `Stream` fetching 1000 at a time.
+    pub fn get_historical_trades_range<S1, S2>(
+        &self, symbol: S1, from_id: u64, to_id: S2,
+    ) -> impl Stream<Item = Result<Trade>> + '_
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+    {
+        let symbol: String = symbol.into();
+        let pager = Pager::new(Cursor::FromId(from_id), to_id.into(), TRADES_LIMIT);
+
+        pages(pager, move |cursor| {
+            let symbol = symbol.clone();
+            async move {
+                let limit = format!("limit={}", TRADES_LIMIT);
+                self.client
+                    .wait_for_weight(&API::Spot(Spot::HistoricalTrades), &limit)
+                    .await?;
+                let Trades::AllTrades(trades) = self
+                    .get_historical_trades(symbol, cursor.id(), TRADES_LIMIT)
+                    .await?;
+                Ok(trades)
+            }
+        })
     }
 }
@@ -1,4 +1,21 @@
-use serde::{Deserialize, Serialize};
+use crate::errors::{BinanceContentError, Error};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A price or quantity. This is `f64` unless the `decimal` feature is enabled, which makes it
+/// `rust_decimal::Decimal` so values go from responses to order requests without rounding.
+#[cfg(not(feature = "decimal"))]
+pub type Amount = f64;
+#[cfg(feature = "decimal")]
+pub type Amount = rust_decimal::Decimal;
+
+#[cfg(not(feature = "decimal"))]
+pub(crate) const ZERO: Amount = 0.0;
+#[cfg(feature = "decimal")]
+pub(crate) const ZERO: Amount = rust_decimal::Decimal::ZERO;
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -33,7 +50,7 @@ pub struct Symbol {
     pub base_asset_precision: u64,
     pub quote_asset: String,
     pub quote_precision: u64,
-    pub order_types: Vec<String>,
+    pub order_types: Vec<OrderType>,
     pub iceberg_allowed: bool,
     pub is_spot_trading_allowed: bool,
     pub is_margin_trading_allowed: bool,
@@ -116,6 +133,158 @@ pub struct Balance {
     pub locked: String,
 }
 
+// A string field with known values, keeping the values Binance adds later in `Other`
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            Other(String),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Other(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    value => $name::Other(value.to_string()),
+                }
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(item: $name) -> Self {
+                item.as_str().to_string()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok($name::from(String::deserialize(deserializer)?.as_str()))
+            }
+        }
+    };
+}
+
+string_enum! {
+    pub enum OrderSide {
+        Buy => "BUY",
+        Sell => "SELL",
+    }
+}
+
+string_enum! {
+    /// The order types of spot and futures. Each market only accepts its own.
+    pub enum OrderType {
+        Limit => "LIMIT",
+        Market => "MARKET",
+        /// Spot only.
+        StopLoss => "STOP_LOSS",
+        StopLossLimit => "STOP_LOSS_LIMIT",
+        TakeProfit => "TAKE_PROFIT",
+        /// Spot only.
+        TakeProfitLimit => "TAKE_PROFIT_LIMIT",
+        /// Spot only.
+        LimitMaker => "LIMIT_MAKER",
+        /// Futures only.
+        Stop => "STOP",
+        /// Futures only.
+        StopMarket => "STOP_MARKET",
+        /// Futures only.
+        TakeProfitMarket => "TAKE_PROFIT_MARKET",
+        /// Futures only.
+        TrailingStopMarket => "TRAILING_STOP_MARKET",
+        /// Futures only: sent for liquidation orders.
+        Liquidation => "LIQUIDATION",
+    }
+}
+
+string_enum! {
+    #[allow(clippy::upper_case_acronyms)]
+    pub enum TimeInForce {
+        GTC => "GTC",
+        IOC => "IOC",
+        FOK => "FOK",
+        /// Futures only: good till crossing, i.e. post only.
+        GTX => "GTX",
+        /// Futures only: good till date.
+        GTD => "GTD",
+    }
+}
+
+string_enum! {
+    pub enum OrderStatus {
+        New => "NEW",
+        PendingNew => "PENDING_NEW",
+        PartiallyFilled => "PARTIALLY_FILLED",
+        Filled => "FILLED",
+        Canceled => "CANCELED",
+        PendingCancel => "PENDING_CANCEL",
+        Rejected => "REJECTED",
+        Expired => "EXPIRED",
+        /// Expired by self-trade prevention.
+        ExpiredInMatch => "EXPIRED_IN_MATCH",
+        /// Futures only: a liquidation with the insurance fund.
+        NewInsurance => "NEW_INSURANCE",
+        /// Futures only: a counterparty liquidation (auto-deleveraging).
+        NewAdl => "NEW_ADL",
+    }
+}
+
+string_enum! {
+    /// Why an order update was sent.
+    pub enum ExecutionType {
+        New => "NEW",
+        Canceled => "CANCELED",
+        /// Spot only: the order was amended by cancel-replace.
+        Replaced => "REPLACED",
+        Rejected => "REJECTED",
+        Trade => "TRADE",
+        Expired => "EXPIRED",
+        /// Spot only: the order expired because of self-trade prevention.
+        TradePrevention => "TRADE_PREVENTION",
+        /// Futures only: an order closed by liquidation or auto-deleveraging.
+        Calculated => "CALCULATED",
+        /// Futures only: the order was modified.
+        Amendment => "AMENDMENT",
+    }
+}
+
+string_enum! {
+    /// Futures only.
+    pub enum PositionSide {
+        Both => "BOTH",
+        Long => "LONG",
+        Short => "SHORT",
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
@@ -123,16 +292,16 @@ pub struct Order {
     pub order_id: u64,
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     pub orig_qty: String,
     pub executed_qty: String,
-    pub status: String,
-    pub time_in_force: String,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
-    pub type_name: String,
-    pub side: String,
+    pub type_name: OrderType,
+    pub side: OrderSide,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     pub iceberg_qty: String,
     pub time: u64,
 }
@@ -154,16 +323,16 @@ pub struct Transaction {
     pub client_order_id: String,
     pub transact_time: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
-    pub status: String,
-    pub time_in_force: String,
-    pub side: String,
+    pub cummulative_quote_qty: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    pub side: OrderSide,
     pub fills: Vec<FillInfo>,
 }
 
@@ -171,14 +340,198 @@ pub struct Transaction {
 #[serde(rename_all = "camelCase")]
 pub struct FillInfo {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
     #[serde(with = "string_or_float")]
-    pub commission: f64,
+    pub commission: Amount,
     pub commission_asset: String,
     pub trade_id: Option<u64>,
 }
+
+/// Response to `Account::place_order`, shaped by the order's `newOrderRespType`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum OrderResponse {
+    Full(OrderFull),
+    Result(OrderResult),
+    Ack(OrderAck),
+}
+
+impl OrderResponse {
+    pub fn symbol(&self) -> &str {
+        match self {
+            OrderResponse::Full(order) => &order.symbol,
+            OrderResponse::Result(order) => &order.symbol,
+            OrderResponse::Ack(order) => &order.symbol,
+        }
+    }
+
+    pub fn order_id(&self) -> u64 {
+        match self {
+            OrderResponse::Full(order) => order.order_id,
+            OrderResponse::Result(order) => order.order_id,
+            OrderResponse::Ack(order) => order.order_id,
+        }
+    }
+
+    pub fn client_order_id(&self) -> &str {
+        match self {
+            OrderResponse::Full(order) => &order.client_order_id,
+            OrderResponse::Result(order) => &order.client_order_id,
+            OrderResponse::Ack(order) => &order.client_order_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAck {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: Option<i64>,
+    pub client_order_id: String,
+    pub transact_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResult {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: Option<i64>,
+    pub client_order_id: String,
+    pub transact_time: u64,
+    #[serde(with = "string_or_float")]
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub orig_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub cummulative_quote_qty: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub type_name: OrderType,
+    pub side: OrderSide,
+    pub working_time: Option<u64>,
+    pub self_trade_prevention_mode: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderFull {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: Option<i64>,
+    pub client_order_id: String,
+    pub transact_time: u64,
+    #[serde(with = "string_or_float")]
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub orig_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub cummulative_quote_qty: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub type_name: OrderType,
+    pub side: OrderSide,
+    pub working_time: Option<u64>,
+    pub self_trade_prevention_mode: Option<String>,
+    pub fills: Vec<FillInfo>,
+}
+
+/// Result of `Account::cancel_replace_order`.
+///
+/// When only one of the two steps succeeded, or both failed, Binance answers with an
+/// error and the failing step holds its error payload.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReplaceResponse {
+    pub cancel_result: CancelReplaceStatus,
+    pub new_order_result: CancelReplaceStatus,
+    pub cancel_response: CancelReplaceOutcome<OrderCanceled>,
+    pub new_order_response: Option<CancelReplaceOutcome<OrderResponse>>,
+}
+
+impl CancelReplaceResponse {
+    pub fn is_success(&self) -> bool {
+        self.cancel_result == CancelReplaceStatus::Success
+            && self.new_order_result == CancelReplaceStatus::Success
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CancelReplaceStatus {
+    Success,
+    Failure,
+    NotAttempted,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum CancelReplaceOutcome<T> {
+    Success(T),
+    Failure(BinanceContentError),
+}
+
+/// An OCO or other order list, returned when placing, cancelling or querying one.
+///
+/// `order_reports` is only sent when placing or cancelling.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: u64,
+    pub contingency_type: String,
+    pub list_status_type: String,
+    pub list_order_status: String,
+    pub list_client_order_id: String,
+    pub transaction_time: u64,
+    pub symbol: String,
+    pub orders: Vec<OrderListOrder>,
+    #[serde(default)]
+    pub order_reports: Vec<OrderReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderReport {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub orig_client_order_id: Option<String>,
+    pub transact_time: u64,
+    #[serde(with = "string_or_float")]
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub orig_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub cummulative_quote_qty: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub type_name: OrderType,
+    pub side: OrderSide,
+    #[serde(default)]
+    #[serde(with = "optional_string_or_float")]
+    pub stop_price: Option<Amount>,
+}
 /// Response to a test order (endpoint /api/v3/order/test).
 ///
 /// Currently, the API responds {} on a successfull test transaction,
@@ -198,25 +551,17 @@ pub struct OrderBook {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Bids {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
-
-    // Never serialized.
-    #[serde(skip)]
-    ignore: Vec<String>,
+    pub qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Asks {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
-
-    // Never serialized.
-    #[serde(skip)]
-    ignore: Vec<String>,
+    pub qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -239,14 +584,14 @@ pub enum Prices {
 pub struct SymbolPrice {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AveragePrice {
     pub mins: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -261,18 +606,236 @@ pub enum KlineSummaries {
     AllKlineSummaries(Vec<KlineSummary>),
 }
 
+/// Kline/candlestick interval.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KlineInterval {
+    #[serde(rename = "1s")]
+    Second1,
+    #[serde(rename = "1m")]
+    Minute1,
+    #[serde(rename = "3m")]
+    Minute3,
+    #[serde(rename = "5m")]
+    Minute5,
+    #[serde(rename = "15m")]
+    Minute15,
+    #[serde(rename = "30m")]
+    Minute30,
+    #[serde(rename = "1h")]
+    Hour1,
+    #[serde(rename = "2h")]
+    Hour2,
+    #[serde(rename = "4h")]
+    Hour4,
+    #[serde(rename = "6h")]
+    Hour6,
+    #[serde(rename = "8h")]
+    Hour8,
+    #[serde(rename = "12h")]
+    Hour12,
+    #[serde(rename = "1d")]
+    Day1,
+    #[serde(rename = "3d")]
+    Day3,
+    #[serde(rename = "1w")]
+    Week1,
+    #[serde(rename = "1M")]
+    Month1,
+}
+
+const MINUTE_MILLIS: u64 = 60_000;
+const DAY_MILLIS: u64 = 24 * 60 * MINUTE_MILLIS;
+
+impl KlineInterval {
+    pub const ALL: [KlineInterval; 16] = [
+        KlineInterval::Second1,
+        KlineInterval::Minute1,
+        KlineInterval::Minute3,
+        KlineInterval::Minute5,
+        KlineInterval::Minute15,
+        KlineInterval::Minute30,
+        KlineInterval::Hour1,
+        KlineInterval::Hour2,
+        KlineInterval::Hour4,
+        KlineInterval::Hour6,
+        KlineInterval::Hour8,
+        KlineInterval::Hour12,
+        KlineInterval::Day1,
+        KlineInterval::Day3,
+        KlineInterval::Week1,
+        KlineInterval::Month1,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            KlineInterval::Second1 => "1s",
+            KlineInterval::Minute1 => "1m",
+            KlineInterval::Minute3 => "3m",
+            KlineInterval::Minute5 => "5m",
+            KlineInterval::Minute15 => "15m",
+            KlineInterval::Minute30 => "30m",
+            KlineInterval::Hour1 => "1h",
+            KlineInterval::Hour2 => "2h",
+            KlineInterval::Hour4 => "4h",
+            KlineInterval::Hour6 => "6h",
+            KlineInterval::Hour8 => "8h",
+            KlineInterval::Hour12 => "12h",
+            KlineInterval::Day1 => "1d",
+            KlineInterval::Day3 => "3d",
+            KlineInterval::Week1 => "1w",
+            KlineInterval::Month1 => "1M",
+        }
+    }
+
+    /// Length of one kline. `None` for `Month1`, whose klines follow calendar months.
+    pub fn duration(self) -> Option<Duration> {
+        let millis = match self {
+            KlineInterval::Second1 => 1000,
+            KlineInterval::Minute1 => MINUTE_MILLIS,
+            KlineInterval::Minute3 => 3 * MINUTE_MILLIS,
+            KlineInterval::Minute5 => 5 * MINUTE_MILLIS,
+            KlineInterval::Minute15 => 15 * MINUTE_MILLIS,
+            KlineInterval::Minute30 => 30 * MINUTE_MILLIS,
+            KlineInterval::Hour1 => 60 * MINUTE_MILLIS,
+            KlineInterval::Hour2 => 2 * 60 * MINUTE_MILLIS,
+            KlineInterval::Hour4 => 4 * 60 * MINUTE_MILLIS,
+            KlineInterval::Hour6 => 6 * 60 * MINUTE_MILLIS,
+            KlineInterval::Hour8 => 8 * 60 * MINUTE_MILLIS,
+            KlineInterval::Hour12 => 12 * 60 * MINUTE_MILLIS,
+            KlineInterval::Day1 => DAY_MILLIS,
+            KlineInterval::Day3 => 3 * DAY_MILLIS,
+            KlineInterval::Week1 => 7 * DAY_MILLIS,
+            KlineInterval::Month1 => return None,
+        };
+        Some(Duration::from_millis(millis))
+    }
+
+    /// Open time of the kline following the one opened at `open_time`, in milliseconds.
+    pub fn next_open_time(self, open_time: u64) -> u64 {
+        match self.duration() {
+            Some(duration) => open_time + duration.as_millis() as u64,
+            None => {
+                let days = open_time / DAY_MILLIS;
+                let (year, month) = civil_from_days(days);
+                let (year, month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+                days_from_civil(year, month) * DAY_MILLIS
+            }
+        }
+    }
+}
+
+impl fmt::Display for KlineInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for KlineInterval {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KlineInterval::ALL
+            .iter()
+            .copied()
+            .find(|interval| interval.as_str() == s)
+            .ok_or_else(|| Error::from(format!("Unknown kline interval: {}", s)))
+    }
+}
+
+impl From<KlineInterval> for String {
+    fn from(item: KlineInterval) -> Self {
+        item.as_str().into()
+    }
+}
+
+// Year and month of a day counted from 1970-01-01, see
+// http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: u64) -> (u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month)
+}
+
+// Days from 1970-01-01 to the first day of a month
+fn days_from_civil(year: u64, month: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Trades {
+    AllTrades(Vec<Trade>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub id: u64,
+    #[serde(with = "string_or_float")]
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub quote_qty: Amount,
+    pub time: u64,
+    pub is_buyer_maker: bool,
+    pub is_best_match: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum AggTrades {
+    AllAggTrades(Vec<AggTrade>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AggTrade {
+    #[serde(rename = "a")]
+    pub agg_id: u64,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Amount,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub qty: Amount,
+    #[serde(rename = "f")]
+    pub first_id: u64,
+    #[serde(rename = "l")]
+    pub last_id: u64,
+    #[serde(rename = "T")]
+    pub time: u64,
+    #[serde(rename = "m")]
+    pub maker: bool,
+    #[serde(rename = "M")]
+    pub best_match: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Tickers {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Amount,
     #[serde(with = "string_or_float")]
-    pub bid_qty: f64,
+    pub bid_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_qty: f64,
+    pub ask_qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -280,9 +843,9 @@ pub struct Tickers {
 pub struct TradeHistory {
     pub id: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
     pub commission: String,
     pub commission_asset: String,
     pub time: u64,
@@ -299,21 +862,21 @@ pub struct PriceStats {
     pub price_change_percent: String,
     pub weighted_avg_price: String,
     #[serde(with = "string_or_float")]
-    pub prev_close_price: f64,
+    pub prev_close_price: Amount,
     #[serde(with = "string_or_float")]
-    pub last_price: f64,
+    pub last_price: Amount,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Amount,
     #[serde(with = "string_or_float")]
-    pub open_price: f64,
+    pub open_price: Amount,
     #[serde(with = "string_or_float")]
-    pub high_price: f64,
+    pub high_price: Amount,
     #[serde(with = "string_or_float")]
-    pub low_price: f64,
+    pub low_price: Amount,
     #[serde(with = "string_or_float")]
-    pub volume: f64,
+    pub volume: Amount,
     pub open_time: u64,
     pub close_time: u64,
     pub first_id: i64,
@@ -357,6 +920,109 @@ pub struct EventBalance {
     pub locked: String,
 }
 
+/// The balances of the assets that changed, sent after each balance change. Replaced
+/// `AccountUpdateEvent` (`outboundAccountInfo`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountPositionEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "u")]
+    pub last_update_time: u64,
+
+    #[serde(rename = "B")]
+    pub balances: Vec<EventBalance>,
+}
+
+/// A deposit, withdrawal or transfer between accounts.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceUpdateEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "a")]
+    pub asset: String,
+
+    #[serde(rename = "d")]
+    pub balance_delta: String,
+
+    #[serde(rename = "T")]
+    pub clear_time: u64,
+}
+
+/// The status of an OCO or other order list, sent along with the `executionReport` of its
+/// orders.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListStatusEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "g")]
+    pub order_list_id: u64,
+
+    #[serde(rename = "c")]
+    pub contingency_type: String,
+
+    #[serde(rename = "l")]
+    pub list_status_type: String,
+
+    #[serde(rename = "L")]
+    pub list_order_status: String,
+
+    #[serde(rename = "r")]
+    pub list_reject_reason: String,
+
+    #[serde(rename = "C")]
+    pub list_client_order_id: String,
+
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+
+    #[serde(rename = "O")]
+    pub orders: Vec<ListStatusOrder>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListStatusOrder {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "i")]
+    pub order_id: u64,
+
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+}
+
+/// The listen key of a user data stream expired. The stream is closed after this event.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKeyExpiredEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    pub listen_key: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderTradeEvent {
@@ -373,13 +1039,13 @@ pub struct OrderTradeEvent {
     pub new_client_order_id: String,
 
     #[serde(rename = "S")]
-    pub side: String,
+    pub side: OrderSide,
 
     #[serde(rename = "o")]
-    pub order_type: String,
+    pub order_type: OrderType,
 
     #[serde(rename = "f")]
-    pub time_in_force: String,
+    pub time_in_force: TimeInForce,
 
     #[serde(rename = "q")]
     pub qty: String,
@@ -400,10 +1066,10 @@ pub struct OrderTradeEvent {
     pub c_ignore: Option<String>,
 
     #[serde(rename = "x")]
-    pub execution_type: String,
+    pub execution_type: ExecutionType,
 
     #[serde(rename = "X")]
-    pub order_status: String,
+    pub order_status: OrderStatus,
 
     #[serde(rename = "r")]
     pub order_reject_reason: String,
@@ -443,6 +1109,22 @@ pub struct OrderTradeEvent {
 
     #[serde(skip, rename = "M")]
     pub m_ignore: bool,
+
+    #[serde(rename = "V")]
+    pub self_trade_prevention_mode: Option<String>,
+
+    // Only sent for the TRADE_PREVENTION execution type and orders expired by it
+    #[serde(rename = "u")]
+    pub trade_group_id: Option<i64>,
+
+    #[serde(rename = "v")]
+    pub prevented_match_id: Option<i64>,
+
+    #[serde(rename = "A")]
+    pub prevented_quantity: Option<String>,
+
+    #[serde(rename = "B")]
+    pub last_prevented_quantity: Option<String>,
 }
 
 /// The Aggregate Trade Streams push trade information that is aggregated for a single taker order.
@@ -648,25 +1330,111 @@ pub struct KlineEvent {
 pub struct KlineSummary {
     pub open_time: i64,
 
-    pub open: f64,
+    pub open: Amount,
 
-    pub high: f64,
+    pub high: Amount,
 
-    pub low: f64,
+    pub low: Amount,
 
-    pub close: f64,
+    pub close: Amount,
 
-    pub volume: f64,
+    pub volume: Amount,
 
     pub close_time: i64,
 
-    pub quote_asset_volume: f64,
+    pub quote_asset_volume: Amount,
 
     pub number_of_trades: i64,
 
-    pub taker_buy_base_asset_volume: f64,
+    pub taker_buy_base_asset_volume: Amount,
+
+    pub taker_buy_quote_asset_volume: Amount,
+}
+
+// Written back in the same array layout it is read from, prices as strings
+impl Serialize for KlineSummary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeTuple;
+
+        #[derive(Serialize)]
+        struct Float<'a>(#[serde(with = "string_or_float")] &'a Amount);
+
+        let mut tuple = serializer.serialize_tuple(11)?;
+        tuple.serialize_element(&self.open_time)?;
+        tuple.serialize_element(&Float(&self.open))?;
+        tuple.serialize_element(&Float(&self.high))?;
+        tuple.serialize_element(&Float(&self.low))?;
+        tuple.serialize_element(&Float(&self.close))?;
+        tuple.serialize_element(&Float(&self.volume))?;
+        tuple.serialize_element(&self.close_time)?;
+        tuple.serialize_element(&Float(&self.quote_asset_volume))?;
+        tuple.serialize_element(&self.number_of_trades)?;
+        tuple.serialize_element(&Float(&self.taker_buy_base_asset_volume))?;
+        tuple.serialize_element(&Float(&self.taker_buy_quote_asset_volume))?;
+        tuple.end()
+    }
+}
+
+// Binance sends each kline as an array: open time, open, high, low, close, volume,
+// close time, quote asset volume, number of trades, taker buy base and quote asset volume
+impl<'de> Deserialize<'de> for KlineSummary {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct KlineSummaryVisitor;
+
+        #[derive(Deserialize)]
+        struct Float(#[serde(with = "string_or_float")] Amount);
+
+        impl<'de> Visitor<'de> for KlineSummaryVisitor {
+            type Value = KlineSummary;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a kline array")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<KlineSummary, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let kline = KlineSummary {
+                    open_time: element(&mut seq, 0, &self)?,
+                    open: element::<_, Float>(&mut seq, 1, &self)?.0,
+                    high: element::<_, Float>(&mut seq, 2, &self)?.0,
+                    low: element::<_, Float>(&mut seq, 3, &self)?.0,
+                    close: element::<_, Float>(&mut seq, 4, &self)?.0,
+                    volume: element::<_, Float>(&mut seq, 5, &self)?.0,
+                    close_time: element(&mut seq, 6, &self)?,
+                    quote_asset_volume: element::<_, Float>(&mut seq, 7, &self)?.0,
+                    number_of_trades: element(&mut seq, 8, &self)?,
+                    taker_buy_base_asset_volume: element::<_, Float>(&mut seq, 9, &self)?.0,
+                    taker_buy_quote_asset_volume: element::<_, Float>(&mut seq, 10, &self)?.0,
+                };
+
+                // Ignore the unused field and any added later
+                while seq.next_element::<de::IgnoredAny>()?.is_some() {}
+
+                Ok(kline)
+            }
+        }
+
+        fn element<'de, A, T>(
+            seq: &mut A, index: usize, expected: &dyn de::Expected,
+        ) -> Result<T, A::Error>
+        where
+            A: SeqAccess<'de>,
+            T: Deserialize<'de>,
+        {
+            seq.next_element()?
+                .ok_or_else(|| de::Error::invalid_length(index, expected))
+        }
 
-    pub taker_buy_quote_asset_volume: f64,
+        deserializer.deserialize_seq(KlineSummaryVisitor)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -749,11 +1517,26 @@ pub struct DepthOrderBookEvent {
     pub asks: Vec<Asks>,
 }
 
+// JSON numbers are only as exact as the `f64` they are parsed into
+#[cfg(not(feature = "decimal"))]
+fn float_amount<E: de::Error>(value: f64) -> Result<Amount, E> {
+    Ok(value)
+}
+
+#[cfg(feature = "decimal")]
+fn float_amount<E: de::Error>(value: f64) -> Result<Amount, E> {
+    use std::convert::TryFrom;
+
+    Amount::try_from(value).map_err(E::custom)
+}
+
 pub(crate) mod string_or_float {
     use std::fmt;
 
     use serde::{de, Serializer, Deserialize, Deserializer};
 
+    use super::{float_amount, Amount};
+
     pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
         T: fmt::Display,
@@ -762,7 +1545,7 @@ pub(crate) mod string_or_float {
         serializer.collect_str(value)
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Amount, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -775,7 +1558,7 @@ pub(crate) mod string_or_float {
 
         match StringOrFloat::deserialize(deserializer)? {
             StringOrFloat::String(s) => s.parse().map_err(de::Error::custom),
-            StringOrFloat::Float(i) => Ok(i),
+            StringOrFloat::Float(i) => float_amount(i),
         }
     }
 }
@@ -785,6 +1568,8 @@ pub(crate) mod optional_string_or_float {
 
     use serde::{de, Serializer, Deserialize, Deserializer};
 
+    use super::{float_amount, Amount};
+
     pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
     where
         T: fmt::Display,
@@ -796,7 +1581,7 @@ pub(crate) mod optional_string_or_float {
         }
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Amount>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -811,7 +1596,7 @@ pub(crate) mod optional_string_or_float {
             None => Ok(None),
             Some(v) => Ok(Some(match v {
                 StringOrFloat::String(s) => s.parse().map_err(de::Error::custom),
-                StringOrFloat::Float(i) => Ok(i),
+                StringOrFloat::Float(i) => float_amount(i),
             }?)),
         }
     }
@@ -0,0 +1,369 @@
+use crate::futures::model as futures_model;
+use crate::model::{self, Amount, ZERO};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+
+/// Levels requested for the snapshot a `LocalOrderBook` is built from.
+pub(crate) const SNAPSHOT_DEPTH: u64 = 1000;
+
+/// A price level: `qty` is the total quantity resting at `price`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceLevel {
+    pub price: Amount,
+    pub qty: Amount,
+}
+
+/// An order book snapshot from `Market::get_custom_depth` or `FuturesMarket::get_custom_depth`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthSnapshot {
+    pub last_update_id: u64,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+/// A depth diff from the `<symbol>@depth` stream. A quantity of 0 removes the level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthUpdate {
+    pub symbol: String,
+    pub first_update_id: u64,
+    pub final_update_id: u64,
+    /// Final update id of the previous event, sent by the futures stream only.
+    pub previous_final_update_id: Option<u64>,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+/// What `LocalOrderBook::push` did with an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateStatus {
+    /// Kept until a snapshot is applied.
+    Buffered,
+    Applied,
+    /// Already part of the book, or for another symbol.
+    Ignored,
+    /// Updates were missed. The book was cleared and needs a new snapshot.
+    Gap,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sequencing {
+    // Each update starts right after the previous one: U == previous u + 1
+    Spot,
+    // Each update names the previous one: pu == previous u
+    Futures,
+}
+
+/// A local copy of one symbol's order book, kept in sync from a depth diff stream
+/// as described in "How to manage a local order book correctly".
+///
+/// Updates pushed before a snapshot is applied are buffered. Once synced, every update
+/// is checked against the previous one and a missed update clears the book until the
+/// next snapshot. The markets' `update_order_book` fetch the snapshots, or feed them
+/// with `apply_snapshot`.
+#[derive(Debug, Clone)]
+pub struct LocalOrderBook {
+    symbol: String,
+    sequencing: Sequencing,
+    bids: BTreeMap<Price, Amount>,
+    asks: BTreeMap<Price, Amount>,
+    last_update_id: u64,
+    synced: bool,
+    // No update applied since the snapshot
+    awaiting_first_update: bool,
+    buffer: VecDeque<DepthUpdate>,
+}
+
+impl LocalOrderBook {
+    /// A book for the spot `<symbol>@depth` and `<symbol>@depth@100ms` streams.
+    pub fn spot<S: Into<String>>(symbol: S) -> Self {
+        LocalOrderBook::new(symbol.into(), Sequencing::Spot)
+    }
+
+    /// A book for the USD-M futures `<symbol>@depth` streams.
+    pub fn futures<S: Into<String>>(symbol: S) -> Self {
+        LocalOrderBook::new(symbol.into(), Sequencing::Futures)
+    }
+
+    fn new(symbol: String, sequencing: Sequencing) -> Self {
+        LocalOrderBook {
+            symbol: symbol.to_uppercase(),
+            sequencing,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            last_update_id: 0,
+            synced: false,
+            awaiting_first_update: false,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Updates are buffered and waiting for a snapshot.
+    pub fn needs_snapshot(&self) -> bool {
+        !self.synced && !self.buffer.is_empty()
+    }
+
+    /// Id of the last update applied, or of the snapshot.
+    pub fn last_update_id(&self) -> u64 {
+        self.last_update_id
+    }
+
+    pub fn push<U: Into<DepthUpdate>>(&mut self, update: U) -> UpdateStatus {
+        let update = update.into();
+        if !update.symbol.eq_ignore_ascii_case(&self.symbol) {
+            return UpdateStatus::Ignored;
+        }
+        if !self.synced {
+            self.buffer.push_back(update);
+            return UpdateStatus::Buffered;
+        }
+
+        let status = self.sequence(&update);
+        if status == UpdateStatus::Gap {
+            self.desync();
+            self.buffer.push_back(update);
+        }
+        status
+    }
+
+    /// Replace the book with `snapshot` and apply the buffered updates that follow it.
+    ///
+    /// Returns whether the book is synced. It is not when the snapshot is older than
+    /// the buffered updates, and a newer snapshot is needed.
+    pub fn apply_snapshot<S: Into<DepthSnapshot>>(&mut self, snapshot: S) -> bool {
+        let snapshot = snapshot.into();
+
+        self.bids = levels(&snapshot.bids);
+        self.asks = levels(&snapshot.asks);
+        self.last_update_id = snapshot.last_update_id;
+        self.synced = true;
+        self.awaiting_first_update = true;
+
+        while let Some(update) = self.buffer.pop_front() {
+            if self.sequence(&update) == UpdateStatus::Gap {
+                self.desync();
+                self.buffer.push_front(update);
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Highest bid.
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids.iter().next_back().map(|(price, qty)| PriceLevel {
+            price: price.0,
+            qty: *qty,
+        })
+    }
+
+    /// Lowest ask.
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks.iter().next().map(|(price, qty)| PriceLevel {
+            price: price.0,
+            qty: *qty,
+        })
+    }
+
+    pub fn spread(&self) -> Option<Amount> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    pub fn mid_price(&self) -> Option<Amount> {
+        Some((self.best_ask()?.price + self.best_bid()?.price) / Amount::from(2u8))
+    }
+
+    /// Bids from the highest price down.
+    pub fn bids(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.bids.iter().rev().map(|(price, qty)| PriceLevel {
+            price: price.0,
+            qty: *qty,
+        })
+    }
+
+    /// Asks from the lowest price up.
+    pub fn asks(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.asks.iter().map(|(price, qty)| PriceLevel {
+            price: price.0,
+            qty: *qty,
+        })
+    }
+
+    /// Quantity bid at exactly `price`, 0 when there is no such level.
+    pub fn bid_qty(&self, price: Amount) -> Amount {
+        self.bids.get(&Price(price)).copied().unwrap_or(ZERO)
+    }
+
+    /// Quantity asked at exactly `price`, 0 when there is no such level.
+    pub fn ask_qty(&self, price: Amount) -> Amount {
+        self.asks.get(&Price(price)).copied().unwrap_or(ZERO)
+    }
+
+    /// Total quantity bid at `price` or higher: what a sell down to `price` could fill.
+    pub fn bid_volume_to(&self, price: Amount) -> Amount {
+        self.bids.range(Price(price)..).map(|(_, qty)| qty).sum()
+    }
+
+    /// Total quantity asked at `price` or lower: what a buy up to `price` could fill.
+    pub fn ask_volume_to(&self, price: Amount) -> Amount {
+        self.asks.range(..=Price(price)).map(|(_, qty)| qty).sum()
+    }
+
+    fn sequence(&mut self, update: &DepthUpdate) -> UpdateStatus {
+        let last = self.last_update_id;
+        let in_sequence = match self.sequencing {
+            Sequencing::Spot => {
+                if update.final_update_id <= last {
+                    return UpdateStatus::Ignored;
+                }
+                if self.awaiting_first_update {
+                    update.first_update_id <= last + 1
+                } else {
+                    update.first_update_id == last + 1
+                }
+            }
+            Sequencing::Futures => {
+                if update.final_update_id < last {
+                    return UpdateStatus::Ignored;
+                }
+                if self.awaiting_first_update {
+                    update.first_update_id <= last
+                } else {
+                    update.previous_final_update_id == Some(last)
+                }
+            }
+        };
+        if !in_sequence {
+            return UpdateStatus::Gap;
+        }
+
+        update_levels(&mut self.bids, &update.bids);
+        update_levels(&mut self.asks, &update.asks);
+        self.last_update_id = update.final_update_id;
+        self.awaiting_first_update = false;
+        UpdateStatus::Applied
+    }
+
+    fn desync(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+        self.synced = false;
+    }
+}
+
+// Prices parsed from the same string are equal, so they can key the levels
+#[derive(Debug, Clone, Copy)]
+struct Price(Amount);
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    #[cfg(not(feature = "decimal"))]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+
+    #[cfg(feature = "decimal")]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+fn levels(levels: &[PriceLevel]) -> BTreeMap<Price, Amount> {
+    let mut book = BTreeMap::new();
+    update_levels(&mut book, levels);
+    book
+}
+
+fn update_levels(book: &mut BTreeMap<Price, Amount>, levels: &[PriceLevel]) {
+    for level in levels {
+        if level.qty == ZERO {
+            book.remove(&Price(level.price));
+        } else {
+            book.insert(Price(level.price), level.qty);
+        }
+    }
+}
+
+fn bid_levels(bids: &[model::Bids]) -> Vec<PriceLevel> {
+    bids.iter()
+        .map(|bid| PriceLevel {
+            price: bid.price,
+            qty: bid.qty,
+        })
+        .collect()
+}
+
+fn ask_levels(asks: &[model::Asks]) -> Vec<PriceLevel> {
+    asks.iter()
+        .map(|ask| PriceLevel {
+            price: ask.price,
+            qty: ask.qty,
+        })
+        .collect()
+}
+
+impl From<model::OrderBook> for DepthSnapshot {
+    fn from(book: model::OrderBook) -> Self {
+        DepthSnapshot {
+            last_update_id: book.last_update_id,
+            bids: bid_levels(&book.bids),
+            asks: ask_levels(&book.asks),
+        }
+    }
+}
+
+impl From<futures_model::OrderBook> for DepthSnapshot {
+    fn from(book: futures_model::OrderBook) -> Self {
+        DepthSnapshot {
+            last_update_id: book.last_update_id,
+            bids: bid_levels(&book.bids),
+            asks: ask_levels(&book.asks),
+        }
+    }
+}
+
+impl From<model::DepthOrderBookEvent> for DepthUpdate {
+    fn from(event: model::DepthOrderBookEvent) -> Self {
+        DepthUpdate {
+            symbol: event.symbol,
+            first_update_id: event.first_update_id,
+            final_update_id: event.final_update_id,
+            previous_final_update_id: None,
+            bids: bid_levels(&event.bids),
+            asks: ask_levels(&event.asks),
+        }
+    }
+}
+
+impl From<futures_model::DepthOrderBookEvent> for DepthUpdate {
+    fn from(event: futures_model::DepthOrderBookEvent) -> Self {
+        DepthUpdate {
+            symbol: event.symbol,
+            first_update_id: event.first_update_id,
+            final_update_id: event.final_update_id,
+            previous_final_update_id: Some(event.previous_final_update_id),
+            bids: bid_levels(&event.bids),
+            asks: ask_levels(&event.asks),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use crate::errors::*;
+use crate::futures::model::AggTrade as FuturesAggTrade;
+use crate::model::{AggTrade, KlineSummary, Trade};
+use futures_util::stream::{self, Stream};
+use std::collections::VecDeque;
+use std::future::Future;
+
+// Largest page each endpoint returns
+pub(crate) const KLINES_LIMIT: u16 = 1000;
+pub(crate) const FUTURES_KLINES_LIMIT: u16 = 1500;
+pub(crate) const TRADES_LIMIT: u16 = 1000;
+
+/// Where the next page starts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Cursor {
+    StartTime(u64),
+    FromId(u64),
+}
+
+impl Cursor {
+    pub(crate) fn time(self) -> Option<u64> {
+        match self {
+            Cursor::StartTime(start_time) => Some(start_time),
+            Cursor::FromId(_) => None,
+        }
+    }
+
+    pub(crate) fn id(self) -> Option<u64> {
+        match self {
+            Cursor::FromId(from_id) => Some(from_id),
+            Cursor::StartTime(_) => None,
+        }
+    }
+}
+
+/// A row of an endpoint that can be walked page by page.
+pub(crate) trait Paged {
+    /// Compared with the end of the requested range: a time or an id.
+    fn position(&self) -> u64;
+    /// The cursor of the page following this row.
+    fn next_cursor(&self) -> Cursor;
+}
+
+impl Paged for KlineSummary {
+    fn position(&self) -> u64 {
+        self.open_time as u64
+    }
+
+    fn next_cursor(&self) -> Cursor {
+        Cursor::StartTime(self.close_time as u64 + 1)
+    }
+}
+
+impl Paged for Trade {
+    fn position(&self) -> u64 {
+        self.id
+    }
+
+    fn next_cursor(&self) -> Cursor {
+        Cursor::FromId(self.id + 1)
+    }
+}
+
+impl Paged for AggTrade {
+    fn position(&self) -> u64 {
+        self.time
+    }
+
+    fn next_cursor(&self) -> Cursor {
+        Cursor::FromId(self.agg_id + 1)
+    }
+}
+
+impl Paged for FuturesAggTrade {
+    fn position(&self) -> u64 {
+        self.time
+    }
+
+    fn next_cursor(&self) -> Cursor {
+        Cursor::FromId(self.agg_id + 1)
+    }
+}
+
+/// Rows fetched but not yet handed out, and where the next page starts.
+pub(crate) struct Pager<T> {
+    rows: VecDeque<T>,
+    cursor: Option<Cursor>,
+    end: Option<u64>,
+    limit: u16,
+}
+
+impl<T: Paged> Pager<T> {
+    pub(crate) fn new(cursor: Cursor, end: Option<u64>, limit: u16) -> Self {
+        Pager {
+            rows: VecDeque::new(),
+            cursor: Some(cursor),
+            end,
+            limit,
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.rows.pop_front()
+    }
+
+    // None once the range is exhausted
+    fn cursor(&self) -> Option<Cursor> {
+        self.cursor
+    }
+
+    fn finish(&mut self) {
+        self.cursor = None;
+    }
+
+    // A short page means there is nothing more to fetch yet
+    fn push(&mut self, page: Vec<T>) {
+        let full = page.len() >= usize::from(self.limit);
+        self.cursor = match page.last() {
+            Some(last) if full => Some(last.next_cursor()),
+            _ => None,
+        };
+
+        for row in page {
+            if matches!(self.end, Some(end) if row.position() > end) {
+                self.cursor = None;
+                break;
+            }
+            self.rows.push_back(row);
+        }
+    }
+}
+
+/// Rows of a paginated endpoint, fetching the next page when the current one is used up.
+///
+/// Stops after the first error.
+#[cfg(feature = "blocking")]
+pub struct Pages<'a, T> {
+    rows: Box<dyn Iterator<Item = Result<T>> + 'a>,
+}
+
+#[cfg(feature = "blocking")]
+impl<'a, T> Pages<'a, T> {
+    pub(crate) fn new<F>(pager: Pager<T>, fetch: F) -> Self
+    where
+        T: Paged + 'a,
+        F: FnMut(Cursor) -> Result<Vec<T>> + 'a,
+    {
+        Pages {
+            rows: Box::new(PageIter { pager, fetch }),
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<'a, T> Iterator for Pages<'a, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next()
+    }
+}
+
+#[cfg(feature = "blocking")]
+struct PageIter<T, F> {
+    pager: Pager<T>,
+    fetch: F,
+}
+
+#[cfg(feature = "blocking")]
+impl<T, F> Iterator for PageIter<T, F>
+where
+    T: Paged,
+    F: FnMut(Cursor) -> Result<Vec<T>>,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.pager.pop() {
+                return Some(Ok(row));
+            }
+
+            let cursor = self.pager.cursor()?;
+            match (self.fetch)(cursor) {
+                Ok(page) => self.pager.push(page),
+                Err(e) => {
+                    self.pager.finish();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// The async counterpart of `Pages`.
+pub(crate) fn pages<'a, T, F, Fut>(pager: Pager<T>, fetch: F) -> impl Stream<Item = Result<T>> + 'a
+where
+    T: Paged + 'a,
+    F: FnMut(Cursor) -> Fut + 'a,
+    Fut: Future<Output = Result<Vec<T>>> + 'a,
+{
+    stream::unfold((pager, fetch), |(mut pager, mut fetch)| async move {
+        loop {
+            if let Some(row) = pager.pop() {
+                return Some((Ok(row), (pager, fetch)));
+            }
+
+            let cursor = pager.cursor()?;
+            match fetch(cursor).await {
+                Ok(page) => pager.push(page),
+                Err(e) => {
+                    pager.finish();
+                    return Some((Err(e), (pager, fetch)));
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,357 @@
+use crate::config::RateLimiter;
+use crate::errors::*;
+use crate::model::RateLimit;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const USED_WEIGHT_HEADER: &str = "x-mbx-used-weight-";
+const ORDER_COUNT_HEADER: &str = "x-mbx-order-count-";
+
+pub const REQUEST_WEIGHT: &str = "REQUEST_WEIGHT";
+pub const ORDERS: &str = "ORDERS";
+
+/// Request weight and order counts reported by Binance in the `X-MBX-USED-WEIGHT-*`
+/// and `X-MBX-ORDER-COUNT-*` response headers.
+///
+/// Clones share the same state, so one `RateLimits` can be handed to several clients
+/// through `Config::set_rate_limits` to track a single IP or account.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimits {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    limits: Vec<RateLimit>,
+    counters: HashMap<Window, Counter>,
+    banned_until: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Window {
+    rate_limit_type: &'static str,
+    interval: &'static str,
+    interval_num: u16,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Counter {
+    count: u64,
+    updated: SystemTime,
+}
+
+// What one request counts against the rate limits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Cost {
+    pub weight: u64,
+    pub orders: u64,
+}
+
+/// Usage of one rate limit in its current window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitUsage {
+    pub rate_limit_type: String,
+    pub interval: String,
+    pub interval_num: u16,
+    pub count: u64,
+    /// Known once seeded from `ExchangeInformation::rate_limits`.
+    pub limit: Option<u64>,
+}
+
+impl PartialEq for RateLimits {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl RateLimits {
+    pub fn new() -> Self {
+        RateLimits::default()
+    }
+
+    /// Set the limits to enforce, usually `ExchangeInformation::rate_limits`.
+    pub fn seed(&self, rate_limits: &[RateLimit]) {
+        self.lock().limits = rate_limits.to_vec();
+    }
+
+    pub fn usage(&self) -> Vec<RateLimitUsage> {
+        let state = self.lock();
+        let now = SystemTime::now();
+        let mut usage: Vec<RateLimitUsage> = Vec::new();
+
+        for (window, counter) in &state.counters {
+            usage.push(RateLimitUsage {
+                rate_limit_type: window.rate_limit_type.into(),
+                interval: window.interval.into(),
+                interval_num: window.interval_num,
+                count: window.current(counter, now),
+                limit: state.limit(window),
+            });
+        }
+        for limit in &state.limits {
+            if let Some(window) = Window::from_rate_limit(limit) {
+                if !state.counters.contains_key(&window) {
+                    usage.push(RateLimitUsage {
+                        rate_limit_type: limit.rate_limit_type.clone(),
+                        interval: limit.interval.clone(),
+                        interval_num: limit.interval_num,
+                        count: 0,
+                        limit: Some(limit.limit),
+                    });
+                }
+            }
+        }
+
+        usage.sort_by(|a, b| {
+            (
+                &a.rate_limit_type,
+                window_length(&a.interval, a.interval_num),
+            )
+                .cmp(&(
+                    &b.rate_limit_type,
+                    window_length(&b.interval, b.interval_num),
+                ))
+        });
+        usage
+    }
+
+    pub fn used_weight(&self) -> Vec<RateLimitUsage> {
+        self.usage()
+            .into_iter()
+            .filter(|usage| usage.rate_limit_type == REQUEST_WEIGHT)
+            .collect()
+    }
+
+    pub fn order_count(&self) -> Vec<RateLimitUsage> {
+        self.usage()
+            .into_iter()
+            .filter(|usage| usage.rate_limit_type == ORDERS)
+            .collect()
+    }
+
+    /// Set after a 429 or 418 response that carried a `Retry-After` header.
+    pub fn banned_until(&self) -> Option<SystemTime> {
+        self.lock()
+            .banned_until
+            .filter(|until| *until > SystemTime::now())
+    }
+
+    pub(crate) fn update(&self, status: StatusCode, headers: &HeaderMap) {
+        let mut state = self.lock();
+        let now = SystemTime::now();
+
+        for (name, value) in headers {
+            let (rate_limit_type, suffix) =
+                if let Some(suffix) = name.as_str().strip_prefix(USED_WEIGHT_HEADER) {
+                    (REQUEST_WEIGHT, suffix)
+                } else if let Some(suffix) = name.as_str().strip_prefix(ORDER_COUNT_HEADER) {
+                    (ORDERS, suffix)
+                } else {
+                    continue;
+                };
+
+            let window = Window::from_header(rate_limit_type, suffix);
+            let count = value
+                .to_str()
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok());
+            if let (Some(window), Some(count)) = (window, count) {
+                state.counters.insert(
+                    window,
+                    Counter {
+                        count,
+                        updated: now,
+                    },
+                );
+            }
+        }
+
+        if status == StatusCode::TOO_MANY_REQUESTS || status.as_u16() == 418 {
+            if let Some(retry_after) = crate::client::retry_after(headers) {
+                state.banned_until = Some(now + retry_after);
+            }
+        }
+    }
+
+    // Reserves `cost` in every window when it fits, otherwise returns how long to wait
+    // (or an error when rejecting). Reserving under the lock counts concurrent requests
+    // too; the response headers then replace the estimate with Binance's own count.
+    pub(crate) fn acquire(&self, limiter: &RateLimiter, cost: Cost) -> Result<Option<Duration>> {
+        if *limiter == RateLimiter::Disabled {
+            return Ok(None);
+        }
+
+        let mut state = self.lock();
+        let now = SystemTime::now();
+        let wait = state.wait(cost, now);
+        if wait == Duration::default() {
+            state.reserve(cost, now);
+            return Ok(None);
+        }
+        match limiter {
+            RateLimiter::Reject => Err(ErrorKind::RateLimitExceeded(wait).into()),
+            _ => Ok(Some(wait)),
+        }
+    }
+
+    // How long until `cost` fits in every window, without reserving it
+    pub(crate) fn wait_time(&self, cost: Cost) -> Duration {
+        self.lock().wait(cost, SystemTime::now())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl State {
+    fn wait(&self, cost: Cost, now: SystemTime) -> Duration {
+        let mut wait = self
+            .banned_until
+            .and_then(|until| until.duration_since(now).ok())
+            .unwrap_or_default();
+
+        for limit in &self.limits {
+            let window = match Window::from_rate_limit(limit) {
+                Some(window) => window,
+                None => continue,
+            };
+            let amount = window.amount(cost);
+            let count = self.count(&window, now);
+            // A request larger than the whole limit only has to wait for an empty window
+            if amount > 0 && count > 0 && count + amount > limit.limit {
+                wait = wait.max(window.remaining(now));
+            }
+        }
+        wait
+    }
+
+    fn reserve(&mut self, cost: Cost, now: SystemTime) {
+        let windows: Vec<Window> = self
+            .limits
+            .iter()
+            .filter_map(Window::from_rate_limit)
+            .collect();
+
+        for window in windows {
+            let amount = window.amount(cost);
+            if amount > 0 {
+                let count = self.count(&window, now) + amount;
+                self.counters.insert(
+                    window,
+                    Counter {
+                        count,
+                        updated: now,
+                    },
+                );
+            }
+        }
+    }
+
+    fn count(&self, window: &Window, now: SystemTime) -> u64 {
+        self.counters
+            .get(window)
+            .map_or(0, |counter| window.current(counter, now))
+    }
+
+    fn limit(&self, window: &Window) -> Option<u64> {
+        self.limits
+            .iter()
+            .find(|limit| Window::from_rate_limit(limit).as_ref() == Some(window))
+            .map(|limit| limit.limit)
+    }
+}
+
+impl Window {
+    // Header suffixes look like `1m`, `10s` or `1d`
+    fn from_header(rate_limit_type: &'static str, suffix: &str) -> Option<Self> {
+        let split = suffix.find(|c: char| !c.is_ascii_digit())?;
+        let (interval_num, unit) = suffix.split_at(split);
+        let interval = match unit.to_ascii_lowercase().as_str() {
+            "s" => "SECOND",
+            "m" => "MINUTE",
+            "h" => "HOUR",
+            "d" => "DAY",
+            _ => return None,
+        };
+
+        Some(Window {
+            rate_limit_type,
+            interval,
+            interval_num: interval_num.parse().ok()?,
+        })
+    }
+
+    fn from_rate_limit(limit: &RateLimit) -> Option<Self> {
+        let rate_limit_type = match limit.rate_limit_type.as_str() {
+            REQUEST_WEIGHT => REQUEST_WEIGHT,
+            ORDERS => ORDERS,
+            _ => return None,
+        };
+        let interval = match limit.interval.as_str() {
+            "SECOND" => "SECOND",
+            "MINUTE" => "MINUTE",
+            "HOUR" => "HOUR",
+            "DAY" => "DAY",
+            _ => return None,
+        };
+
+        Some(Window {
+            rate_limit_type,
+            interval,
+            interval_num: limit.interval_num,
+        })
+    }
+
+    fn amount(&self, cost: Cost) -> u64 {
+        if self.rate_limit_type == ORDERS {
+            cost.orders
+        } else {
+            cost.weight
+        }
+    }
+
+    fn length(&self) -> u64 {
+        window_length(self.interval, self.interval_num)
+    }
+
+    // Binance counts in fixed windows aligned to the clock, so a count from a
+    // previous window no longer applies
+    fn current(&self, counter: &Counter, now: SystemTime) -> u64 {
+        if window_index(counter.updated, self.length()) == window_index(now, self.length()) {
+            counter.count
+        } else {
+            0
+        }
+    }
+
+    fn remaining(&self, now: SystemTime) -> Duration {
+        let length = self.length();
+        let end = (window_index(now, length) + 1) * length;
+        let elapsed = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        Duration::from_secs(end)
+            .checked_sub(elapsed)
+            .unwrap_or_default()
+    }
+}
+
+fn window_length(interval: &str, interval_num: u16) -> u64 {
+    let unit = match interval {
+        "SECOND" => 1,
+        "MINUTE" => 60,
+        "HOUR" => 3600,
+        _ => 86400,
+    };
+    (unit * u64::from(interval_num)).max(1)
+}
+
+fn window_index(time: SystemTime, length: u64) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / length
+}
@@ -0,0 +1,99 @@
+use crate::errors::*;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ed25519_dalek::SigningKey;
+use ed25519_dalek::Signer as _;
+use hex::encode as hex_encode;
+use hmac::{Hmac, Mac, NewMac};
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+use sha2::Sha256;
+use url::form_urlencoded::byte_serialize;
+
+/// Signs the query string of `SIGNED` endpoints.
+///
+/// The returned value is appended to the request as `signature=`, so it must
+/// already be URL safe.
+pub trait Signer: Send + Sync {
+    fn sign(&self, payload: &str) -> Result<String>;
+}
+
+/// HMAC-SHA256 with the API secret key, hex encoded.
+#[derive(Clone)]
+pub struct HmacSigner {
+    secret_key: String,
+}
+
+impl HmacSigner {
+    pub fn new<S: Into<String>>(secret_key: S) -> Self {
+        HmacSigner {
+            secret_key: secret_key.into(),
+        }
+    }
+}
+
+impl Signer for HmacSigner {
+    fn sign(&self, payload: &str) -> Result<String> {
+        let mut signed_key = Hmac::<Sha256>::new_varkey(self.secret_key.as_bytes())
+            .map_err(|_| Error::from("Invalid HMAC secret key"))?;
+        signed_key.update(payload.as_bytes());
+
+        Ok(hex_encode(signed_key.finalize().into_bytes()))
+    }
+}
+
+/// Ed25519 with a private key registered on Binance, base64 encoded.
+#[derive(Clone)]
+pub struct Ed25519Signer {
+    key: SigningKey,
+}
+
+impl Ed25519Signer {
+    pub fn from_pkcs8_pem(pem: &str) -> Result<Self> {
+        let key = SigningKey::from_pkcs8_pem(pem)
+            .map_err(|e| Error::from(format!("Invalid Ed25519 private key: {}", e)))?;
+
+        Ok(Ed25519Signer { key })
+    }
+}
+
+impl Signer for Ed25519Signer {
+    fn sign(&self, payload: &str) -> Result<String> {
+        let signature = self.key.sign(payload.as_bytes());
+
+        Ok(url_encode(&BASE64.encode(signature.to_bytes())))
+    }
+}
+
+/// RSASSA-PKCS1-v1_5 with SHA-256 and a private key registered on Binance, base64 encoded.
+#[derive(Clone)]
+pub struct RsaSigner {
+    key: RsaPrivateKey,
+}
+
+impl RsaSigner {
+    pub fn from_pkcs8_pem(pem: &str) -> Result<Self> {
+        let key = RsaPrivateKey::from_pkcs8_pem(pem)
+            .map_err(|e| Error::from(format!("Invalid RSA private key: {}", e)))?;
+
+        Ok(RsaSigner { key })
+    }
+}
+
+impl Signer for RsaSigner {
+    fn sign(&self, payload: &str) -> Result<String> {
+        use rsa::sha2::{Digest, Sha256};
+
+        let digest = Sha256::digest(payload.as_bytes());
+        let signature = self
+            .key
+            .sign(Pkcs1v15Sign::new::<Sha256>(), &digest)
+            .map_err(|e| Error::from(format!("RSA signing failed: {}", e)))?;
+
+        Ok(url_encode(&BASE64.encode(signature)))
+    }
+}
+
+fn url_encode(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
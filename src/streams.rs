@@ -0,0 +1,192 @@
+use crate::errors::*;
+use crate::model::KlineInterval;
+
+/// How often a depth or mark price stream pushes an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateSpeed {
+    /// 1000ms for spot depth, 250ms for futures depth and 3s for mark prices.
+    Default,
+    Millis100,
+    Millis250,
+    Millis500,
+    Millis1000,
+}
+
+/// The contract of a pair followed by a continuous kline stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractType {
+    Perpetual,
+    CurrentQuarter,
+    NextQuarter,
+}
+
+impl ContractType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContractType::Perpetual => "perpetual",
+            ContractType::CurrentQuarter => "current_quarter",
+            ContractType::NextQuarter => "next_quarter",
+        }
+    }
+}
+
+/// A market data or user data stream, rendered to its name with `spot_name` or
+/// `futures_name`.
+///
+/// Symbols are lowercased. Streams only one market has fail to render for the other.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamName {
+    AggTrade(String),
+    /// Spot only.
+    Trade(String),
+    Kline(String, KlineInterval),
+    /// Futures only: klines of the contract of a pair.
+    ContinuousKline(String, ContractType, KlineInterval),
+    MiniTicker(String),
+    AllMiniTickers,
+    Ticker(String),
+    AllTickers,
+    BookTicker(String),
+    /// Futures only.
+    AllBookTickers,
+    /// The top 5, 10 or 20 levels of the order book.
+    PartialDepth(String, u8, UpdateSpeed),
+    /// Order book diffs, to keep a `LocalOrderBook` in sync.
+    Depth(String, UpdateSpeed),
+    /// Futures only.
+    MarkPrice(String, UpdateSpeed),
+    /// Futures only.
+    AllMarkPrices(UpdateSpeed),
+    /// Futures only: liquidation orders.
+    ForceOrder(String),
+    /// Futures only.
+    AllForceOrders,
+    /// Futures only: the composition of a composite index symbol.
+    CompositeIndex(String),
+    /// Futures only: listings, settlements and notional bracket changes of contracts.
+    ContractInfo,
+    /// The user data stream of a listen key.
+    UserData(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Venue {
+    Spot,
+    Futures,
+}
+
+impl StreamName {
+    pub fn spot_name(&self) -> Result<String> {
+        self.name(Venue::Spot)
+    }
+
+    pub fn futures_name(&self) -> Result<String> {
+        self.name(Venue::Futures)
+    }
+
+    fn name(&self, venue: Venue) -> Result<String> {
+        if let Some(only) = self.only_on() {
+            if only != venue {
+                bail!(format!("{:?} is only available on {:?}", self, only));
+            }
+        }
+
+        let name = match self {
+            StreamName::AggTrade(symbol) => format!("{}@aggTrade", symbol.to_lowercase()),
+            StreamName::Trade(symbol) => format!("{}@trade", symbol.to_lowercase()),
+            StreamName::Kline(symbol, interval) => {
+                if venue == Venue::Futures && *interval == KlineInterval::Second1 {
+                    bail!("1s klines are only available on spot");
+                }
+                format!("{}@kline_{}", symbol.to_lowercase(), interval)
+            }
+            StreamName::ContinuousKline(pair, contract_type, interval) => format!(
+                "{}_{}@continuousKline_{}",
+                pair.to_lowercase(),
+                contract_type.as_str(),
+                interval
+            ),
+            StreamName::MiniTicker(symbol) => format!("{}@miniTicker", symbol.to_lowercase()),
+            StreamName::AllMiniTickers => "!miniTicker@arr".into(),
+            StreamName::Ticker(symbol) => format!("{}@ticker", symbol.to_lowercase()),
+            StreamName::AllTickers => "!ticker@arr".into(),
+            StreamName::BookTicker(symbol) => format!("{}@bookTicker", symbol.to_lowercase()),
+            StreamName::AllBookTickers => "!bookTicker".into(),
+            StreamName::PartialDepth(symbol, levels, speed) => {
+                if ![5, 10, 20].contains(levels) {
+                    bail!(format!(
+                        "Partial depth has 5, 10 or 20 levels, not {}",
+                        levels
+                    ));
+                }
+                let speed = depth_speed(venue, *speed)?;
+                format!("{}@depth{}{}", symbol.to_lowercase(), levels, speed)
+            }
+            StreamName::Depth(symbol, speed) => {
+                format!(
+                    "{}@depth{}",
+                    symbol.to_lowercase(),
+                    depth_speed(venue, *speed)?
+                )
+            }
+            StreamName::MarkPrice(symbol, speed) => {
+                format!(
+                    "{}@markPrice{}",
+                    symbol.to_lowercase(),
+                    mark_price_speed(*speed)?
+                )
+            }
+            StreamName::AllMarkPrices(speed) => {
+                format!("!markPrice@arr{}", mark_price_speed(*speed)?)
+            }
+            StreamName::ForceOrder(symbol) => format!("{}@forceOrder", symbol.to_lowercase()),
+            StreamName::AllForceOrders => "!forceOrder@arr".into(),
+            StreamName::CompositeIndex(symbol) => {
+                format!("{}@compositeIndex", symbol.to_lowercase())
+            }
+            StreamName::ContractInfo => "!contractInfo".into(),
+            StreamName::UserData(listen_key) => listen_key.clone(),
+        };
+        Ok(name)
+    }
+
+    // The market a stream is limited to
+    fn only_on(&self) -> Option<Venue> {
+        match self {
+            StreamName::Trade(_) => Some(Venue::Spot),
+            StreamName::ContinuousKline(..)
+            | StreamName::AllBookTickers
+            | StreamName::MarkPrice(..)
+            | StreamName::AllMarkPrices(_)
+            | StreamName::ForceOrder(_)
+            | StreamName::AllForceOrders
+            | StreamName::CompositeIndex(_)
+            | StreamName::ContractInfo => Some(Venue::Futures),
+            _ => None,
+        }
+    }
+}
+
+// The suffix of a depth stream pushing at `speed`
+fn depth_speed(venue: Venue, speed: UpdateSpeed) -> Result<&'static str> {
+    let suffix = match (venue, speed) {
+        (_, UpdateSpeed::Millis100) => "@100ms",
+        (Venue::Spot, UpdateSpeed::Default) | (Venue::Spot, UpdateSpeed::Millis1000) => "",
+        (Venue::Futures, UpdateSpeed::Default) | (Venue::Futures, UpdateSpeed::Millis250) => "",
+        (Venue::Futures, UpdateSpeed::Millis500) => "@500ms",
+        (venue, speed) => bail!(format!(
+            "{:?} depth streams can't push at {:?}",
+            venue, speed
+        )),
+    };
+    Ok(suffix)
+}
+
+// The suffix of a mark price stream pushing at `speed`
+fn mark_price_speed(speed: UpdateSpeed) -> Result<&'static str> {
+    match speed {
+        UpdateSpeed::Default => Ok(""),
+        UpdateSpeed::Millis1000 => Ok("@1s"),
+        speed => bail!(format!("Mark price streams can't push at {:?}", speed)),
+    }
+}
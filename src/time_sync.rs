@@ -0,0 +1,91 @@
+use crate::errors::*;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Offset between the Binance server clock and the local clock, used to stamp
+/// signed requests so they stay inside `recvWindow` despite local clock drift.
+///
+/// Clones share the same offset.
+#[derive(Clone, Debug, Default)]
+pub struct TimeSync {
+    state: Arc<Mutex<Option<Offset>>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Offset {
+    millis: i64,
+    synced_at: Instant,
+}
+
+impl TimeSync {
+    pub fn new() -> Self {
+        TimeSync::default()
+    }
+
+    /// Server time minus local time in milliseconds, once measured.
+    pub fn offset(&self) -> Option<i64> {
+        self.lock().map(|offset| offset.millis)
+    }
+
+    /// The current server time as estimated from the local clock.
+    pub fn timestamp(&self) -> Result<u64> {
+        let local = millis_since_epoch(SystemTime::now())? as i64;
+        let offset = self.offset().unwrap_or(0);
+
+        Ok((local + offset).max(0) as u64)
+    }
+
+    pub(crate) fn is_stale(&self, interval: Option<Duration>) -> bool {
+        match (interval, *self.lock()) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(interval), Some(offset)) => offset.synced_at.elapsed() >= interval,
+        }
+    }
+
+    // The server stamps its response somewhere during the round trip, so compare
+    // it with the local time halfway through
+    pub(crate) fn update(&self, sent: SystemTime, received: SystemTime, server_time: u64) {
+        let (sent, received) = match (millis_since_epoch(sent), millis_since_epoch(received)) {
+            (Ok(sent), Ok(received)) => (sent, received.max(sent)),
+            _ => return,
+        };
+        let local = sent + (received - sent) / 2;
+
+        *self.lock() = Some(Offset {
+            millis: server_time as i64 - local as i64,
+            synced_at: Instant::now(),
+        });
+    }
+
+    // Replace the `timestamp` set by `build_signed_request` with the corrected one
+    pub(crate) fn stamp(&self, request: &str) -> Result<String> {
+        if self.offset().is_none() {
+            return Ok(request.into());
+        }
+
+        let timestamp = format!("timestamp={}", self.timestamp()?);
+        let params: Vec<&str> = request
+            .split('&')
+            .map(|param| {
+                if param.starts_with("timestamp=") {
+                    timestamp.as_str()
+                } else {
+                    param
+                }
+            })
+            .collect();
+
+        Ok(params.join("&"))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<Offset>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn millis_since_epoch(time: SystemTime) -> Result<u64> {
+    let since_epoch = time.duration_since(UNIX_EPOCH)?;
+
+    Ok(since_epoch.as_secs() * 1000 + u64::from(since_epoch.subsec_nanos()) / 1_000_000)
+}
@@ -1,8 +1,20 @@
 use crate::model::*;
 use crate::client::*;
+use crate::config::{Config, ReconnectPolicy};
 use crate::errors::*;
 use crate::api::API;
 use crate::api::Spot;
+use crate::websockets::{EventStream, WebsocketEvent};
+use futures_util::future::{self, Either};
+use futures_util::stream::{self, Stream, StreamExt};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::{Instant, Interval};
+
+/// How often a managed user data stream keeps its listen key alive. Keys expire 60 minutes
+/// after they were created or last kept alive.
+pub const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
 
 #[derive(Clone)]
 pub struct UserStream {
@@ -10,25 +22,6 @@ pub struct UserStream {
     pub recv_window: u64,
 }
 
-#[cfg(feature = "blocking")]
-impl UserStream {
-    // User Stream
-    pub fn start(&self) -> Result<UserDataStream> {
-        self.client.post(API::Spot(Spot::UserDataStream))
-    }
-
-    // Current open orders on a symbol
-    pub fn keep_alive(&self, listen_key: &str) -> Result<Success> {
-        self.client.put(API::Spot(Spot::UserDataStream), listen_key)
-    }
-
-    pub fn close(&self, listen_key: &str) -> Result<Success> {
-        self.client
-            .delete(API::Spot(Spot::UserDataStream), listen_key)
-    }
-}
-
-#[cfg(not(feature = "blocking"))]
 impl UserStream {
     // User Stream
     pub async fn start(&self) -> Result<UserDataStream> {
@@ -48,3 +41,194 @@ impl UserStream {
             .await
     }
 }
+
+/// A user data stream that owns its listen key, as a single stream of events.
+///
+/// The key is kept alive every `KEEP_ALIVE_INTERVAL`. When it expires or Binance no longer
+/// knows it (-1125), a new key is created and the stream reconnected to it. Reconnections
+/// are announced with `Disconnected` and `Reconnected` and follow the `ReconnectPolicy`;
+/// the stream ends after the policy gives up.
+pub struct UserDataSession {
+    user_stream: UserStream,
+    keep_alive_interval: Duration,
+    reconnect_policy: ReconnectPolicy,
+    events: Option<Pin<Box<dyn Stream<Item = Result<WebsocketEvent>> + Send>>>,
+}
+
+impl UserDataSession {
+    pub fn new(user_stream: UserStream) -> UserDataSession {
+        UserDataSession {
+            user_stream,
+            keep_alive_interval: KEEP_ALIVE_INTERVAL,
+            reconnect_policy: ReconnectPolicy::default(),
+            events: None,
+        }
+    }
+
+    pub fn set_keep_alive_interval(mut self, keep_alive_interval: Duration) -> Self {
+        self.keep_alive_interval = keep_alive_interval;
+        self
+    }
+
+    pub fn set_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = reconnect_policy;
+        self
+    }
+
+    /// Create a listen key and connect to its stream.
+    pub async fn connect(&mut self) -> Result<()> {
+        self.connect_config(None).await
+    }
+
+    pub async fn connect_with_config(&mut self, config: &Config) -> Result<()> {
+        self.connect_config(Some(config.clone())).await
+    }
+
+    async fn connect_config(&mut self, config: Option<Config>) -> Result<()> {
+        let listen_key = self.user_stream.start().await?.listen_key;
+        let events = connect_events(&listen_key, config.as_ref()).await?;
+        let session = Session {
+            user_stream: self.user_stream.clone(),
+            config,
+            listen_key,
+            events,
+            keep_alive: keep_alive_timer(self.keep_alive_interval),
+            keep_alive_interval: self.keep_alive_interval,
+            reconnect_policy: self.reconnect_policy.clone(),
+            reconnect: None,
+            closed: false,
+        };
+        self.events = Some(Box::pin(stream::unfold(
+            session,
+            |mut session| async move {
+                let event = session.next().await?;
+                Some((event, session))
+            },
+        )));
+        Ok(())
+    }
+
+    /// The listen key in use is only known to the stream, so close it with
+    /// `UserStream::close` if needed; it expires on its own otherwise.
+    pub fn disconnect(&mut self) {
+        self.events = None;
+    }
+}
+
+impl Stream for UserDataSession {
+    type Item = Result<WebsocketEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.events.as_mut() {
+            Some(events) => events.as_mut().poll_next(cx),
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+// The state of a connected `UserDataSession`
+struct Session {
+    user_stream: UserStream,
+    config: Option<Config>,
+    listen_key: String,
+    events: EventStream,
+    keep_alive: Interval,
+    keep_alive_interval: Duration,
+    reconnect_policy: ReconnectPolicy,
+    // Set after a disconnection, to whether the listen key must be replaced
+    reconnect: Option<bool>,
+    closed: bool,
+}
+
+impl Session {
+    async fn next(&mut self) -> Option<Result<WebsocketEvent>> {
+        loop {
+            if self.closed {
+                return None;
+            }
+            if let Some(renew) = self.reconnect.take() {
+                return Some(self.reconnect(renew).await);
+            }
+
+            // None when it's time to keep the listen key alive
+            let tick = Box::pin(self.keep_alive.tick());
+            let event = match future::select(self.events.next(), tick).await {
+                Either::Left((event, _)) => Some(event),
+                Either::Right(_) => None,
+            };
+            let event = match event {
+                Some(event) => event,
+                None => match self.user_stream.keep_alive(&self.listen_key).await {
+                    Ok(_) => continue,
+                    Err(e) if is_invalid_listen_key(&e) => {
+                        return Some(self.disconnected(e.to_string(), true));
+                    }
+                    Err(e) => return Some(Err(e)),
+                },
+            };
+            return Some(match event {
+                Some(Ok(WebsocketEvent::ListenKeyExpired(_))) => {
+                    self.disconnected("Listen key expired".into(), true)
+                }
+                Some(Err(e)) if self.events.is_closed() => self.disconnected(e.to_string(), false),
+                Some(event) => event,
+                None => self.disconnected("Disconnected".into(), false),
+            });
+        }
+    }
+
+    fn disconnected(&mut self, reason: String, renew: bool) -> Result<WebsocketEvent> {
+        self.reconnect = Some(renew);
+        Ok(WebsocketEvent::Disconnected(reason))
+    }
+
+    async fn reconnect(&mut self, mut renew: bool) -> Result<WebsocketEvent> {
+        // The connection may still be open after the listen key expired
+        let _ = self.events.close().await;
+        let mut attempt = 1;
+        loop {
+            tokio::time::sleep(self.reconnect_policy.backoff(attempt)).await;
+            match self.resume(&mut renew).await {
+                Ok(()) => return Ok(WebsocketEvent::Reconnected),
+                Err(e) if !self.reconnect_policy.can_retry(attempt) => {
+                    self.closed = true;
+                    return Err(e);
+                }
+                Err(_) => attempt += 1,
+            }
+        }
+    }
+
+    // Reconnects with the same listen key, unless `renew` is set or it is no longer valid
+    async fn resume(&mut self, renew: &mut bool) -> Result<()> {
+        if !*renew {
+            match self.user_stream.keep_alive(&self.listen_key).await {
+                Ok(_) => (),
+                Err(e) if is_invalid_listen_key(&e) => *renew = true,
+                Err(e) => return Err(e),
+            }
+        }
+        if *renew {
+            self.listen_key = self.user_stream.start().await?.listen_key;
+            *renew = false;
+        }
+        self.events = connect_events(&self.listen_key, self.config.as_ref()).await?;
+        self.keep_alive = keep_alive_timer(self.keep_alive_interval);
+        Ok(())
+    }
+}
+
+async fn connect_events(listen_key: &str, config: Option<&Config>) -> Result<EventStream> {
+    match config {
+        Some(config) => EventStream::connect_with_config(listen_key, config).await,
+        None => EventStream::connect(listen_key).await,
+    }
+}
+
+pub(crate) fn keep_alive_timer(interval: Duration) -> Interval {
+    tokio::time::interval_at(Instant::now() + interval, interval)
+}
+
+pub(crate) fn is_invalid_listen_key(error: &Error) -> bool {
+    error.binance_code() == Some(BinanceErrorCode::InvalidListenKey)
+}
@@ -1,7 +1,6 @@
 use crate::errors::*;
 use std::collections::BTreeMap;
 use std::time::{SystemTime, UNIX_EPOCH};
-use serde_json::Value;
 
 pub fn build_request(parameters: &BTreeMap<String, String>) -> String {
     let mut request = String::new();
@@ -37,14 +36,6 @@ pub fn build_signed_request(
     }
 }
 
-pub fn to_i64(v: &Value) -> i64 {
-    v.as_i64().unwrap()
-}
-
-pub fn to_f64(v: &Value) -> f64 {
-    v.as_str().unwrap().parse().unwrap()
-}
-
 fn get_timestamp() -> Result<u64> {
     let start = SystemTime::now();
     let since_epoch = start.duration_since(UNIX_EPOCH)?;
@@ -1,36 +1,57 @@
 use crate::errors::*;
 use crate::config::*;
 use crate::model::*;
+pub use crate::streams::{ContractType, StreamName, UpdateSpeed};
 use url::Url;
-use serde_json::from_str;
+use serde_json::{from_str, from_value, Value};
 use serde::{Deserialize, Serialize};
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use futures_util::{ready, SinkExt, Stream, StreamExt};
+use std::{
+    collections::{BTreeSet, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll},
+    thread,
+    time::{Duration, Instant},
+};
 use tungstenite::{connect, Message};
 use tungstenite::protocol::WebSocket;
 use tungstenite::client::AutoStream;
 use tungstenite::handshake::client::Response;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+use tokio::net::TcpStream;
 
 static WEBSOCKET_URL: &str = "wss://stream.binance.com:9443/ws/";
 static WEBSOCKET_MULTI_STREAM: &str = "wss://stream.binance.com:9443/stream?streams="; // <streamName1>/<streamName2>/<streamName3>
 
-static OUTBOUND_ACCOUNT_INFO: &str = "outboundAccountInfo";
-static EXECUTION_REPORT: &str = "executionReport";
+const OUTBOUND_ACCOUNT_INFO: &str = "outboundAccountInfo";
+const OUTBOUND_ACCOUNT_POSITION: &str = "outboundAccountPosition";
+const BALANCE_UPDATE: &str = "balanceUpdate";
+const LIST_STATUS: &str = "listStatus";
+const EXECUTION_REPORT: &str = "executionReport";
+const LISTEN_KEY_EXPIRED: &str = "listenKeyExpired";
 
-static KLINE: &str = "kline";
-static AGGREGATED_TRADE: &str = "aggTrade";
-static TRADE: &str = "trade";
-static DEPTH_ORDERBOOK: &str = "depthUpdate";
-static PARTIAL_ORDERBOOK: &str = "lastUpdateId";
-static STREAM: &str = "stream";
+const KLINE: &str = "kline";
+const AGGREGATED_TRADE: &str = "aggTrade";
+const TRADE: &str = "trade";
+const DEPTH_ORDERBOOK: &str = "depthUpdate";
+const PARTIAL_ORDERBOOK: &str = "lastUpdateId";
+const BOOK_TICKER: &str = "@bookTicker";
 
-static DAYTICKER: &str = "24hrTicker";
+const DAYTICKER: &str = "24hrTicker";
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum WebsocketEvent {
+    /// Sent by `outboundAccountInfo`, which `outboundAccountPosition` replaced.
     AccountUpdate(AccountUpdateEvent),
+    AccountPosition(AccountPositionEvent),
+    BalanceUpdate(BalanceUpdateEvent),
     OrderTrade(OrderTradeEvent),
+    ListStatus(ListStatusEvent),
+    ListenKeyExpired(ListenKeyExpiredEvent),
     AggrTrades(AggrTradesEvent),
     Trade(TradeEvent),
     OrderBook(OrderBook),
@@ -39,12 +60,20 @@ pub enum WebsocketEvent {
     Kline(KlineEvent),
     DepthOrderBook(DepthOrderBookEvent),
     BookTicker(BookTickerEvent),
+    /// An event of a type this crate doesn't decode.
+    Unknown(Value),
+    /// The connection was lost and a `ReconnectPolicy` is reconnecting, with the reason.
+    Disconnected(String),
+    /// The connection was re-established with the same streams.
+    Reconnected,
 }
 
 pub struct WebSockets<'a> {
     pub socket: Option<(WebSocket<AutoStream>, Response)>,
     handler: Box<dyn FnMut(WebsocketEvent) -> Result<()> + 'a>,
-    subscription: &'a str,
+    url: String,
+    reconnect_policy: Option<ReconnectPolicy>,
+    subscriptions: Subscriptions,
 }
 
 impl<'a> WebSockets<'a> {
@@ -55,49 +84,48 @@ impl<'a> WebSockets<'a> {
         WebSockets {
             socket: None,
             handler: Box::new(handler),
-            subscription: "",
+            url: String::new(),
+            reconnect_policy: None,
+            subscriptions: Subscriptions::default(),
         }
     }
 
+    /// Reconnect from `event_loop` when the connection is closed or fails, instead of
+    /// returning an error.
+    pub fn set_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = Some(reconnect_policy);
+        self
+    }
+
     pub fn connect(&mut self, subscription: &'a str) -> Result<()> {
-        self.subscription = subscription;
+        self.subscriptions = Subscriptions::new(&[subscription])?;
         let wss: String = format!("{}{}", WEBSOCKET_URL, subscription);
-        let url = Url::parse(&wss)?;
-
-        match connect(url) {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => {
-                bail!(format!("Error during handshake {}", e));
-            }
-        }
+        self.connect_url(wss)
     }
 
     pub fn connect_with_config(&mut self, subscription: &'a str, config: &'a Config) -> Result<()> {
-        self.subscription = subscription;
+        self.subscriptions = Subscriptions::new(&[subscription])?;
         let wss: String = format!("{}{}", &config.ws_endpoint, subscription);
-        let url = Url::parse(&wss)?;
-
-        match connect(url) {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => {
-                bail!(format!("Error during handshake {}", e));
-            }
-        }
+        self.connect_url(wss)
     }
 
     pub fn connect_multiple_streams(&mut self, endpoints: &[String]) -> Result<()> {
+        self.subscriptions = Subscriptions::new(endpoints)?;
         let wss: String = format!("{}{}", WEBSOCKET_MULTI_STREAM, endpoints.join("/"));
+        self.connect_url(wss)
+    }
+
+    pub fn connect_streams(&mut self, streams: &[StreamName]) -> Result<()> {
+        self.connect_multiple_streams(&spot_names(streams)?)
+    }
+
+    fn connect_url(&mut self, wss: String) -> Result<()> {
         let url = Url::parse(&wss)?;
 
         match connect(url) {
             Ok(answer) => {
                 self.socket = Some(answer);
+                self.url = wss;
                 Ok(())
             }
             Err(e) => {
@@ -115,59 +143,109 @@ impl<'a> WebSockets<'a> {
         }
     }
 
+    /// Start listening to `streams` on the open connection.
+    pub fn subscribe<S: AsRef<str>>(&mut self, streams: &[S]) -> Result<()> {
+        let request = self.subscriptions.subscribe(streams)?;
+        self.request(request)?;
+        Ok(())
+    }
+
+    pub fn unsubscribe<S: AsRef<str>>(&mut self, streams: &[S]) -> Result<()> {
+        let request = self.subscriptions.unsubscribe(streams);
+        self.request(request)?;
+        Ok(())
+    }
+
+    /// The streams Binance reports the connection listening to.
+    pub fn list_subscriptions(&mut self) -> Result<Vec<String>> {
+        let request = self.subscriptions.list_subscriptions();
+        Ok(serde_json::from_value(self.request(request)?)?)
+    }
+
+    /// Wrap the events in `{"stream": ..., "data": ...}`, as combined streams do.
+    pub fn set_combined(&mut self, combined: bool) -> Result<()> {
+        let request = self.subscriptions.set_combined(combined);
+        self.request(request)?;
+        Ok(())
+    }
+
+    // Events received while waiting for the response are handled after it
+    fn request(&mut self, request: Request) -> Result<serde_json::Value> {
+        thread::sleep(self.subscriptions.delay());
+        let mut received = Vec::new();
+        let result = match self.socket {
+            Some(ref mut socket) => send_request_blocking(
+                &mut socket.0,
+                &mut self.subscriptions,
+                &request,
+                &mut received,
+            ),
+            None => Err("Not connected".into()),
+        };
+        for msg in received {
+            if let Err(e) = self.handle_msg(&msg) {
+                bail!(format!("Error on handling stream message: {}", e));
+            }
+        }
+        result
+    }
+
+    // Subscriptions made since connecting are lost with the connection
+    fn restore_subscriptions(&mut self) -> Result<()> {
+        let (subscribe, unsubscribe) = self.subscriptions.changes();
+        if !subscribe.is_empty() {
+            self.subscribe(&subscribe)?;
+        }
+        if !unsubscribe.is_empty() {
+            self.unsubscribe(&unsubscribe)?;
+        }
+        Ok(())
+    }
+
     fn handle_msg(&mut self, msg: &str) -> Result<()> {
-        let value: serde_json::Value = serde_json::from_str(msg)?;
-        if msg.find(STREAM) != None {
-            if value["data"] != serde_json::Value::Null {
-                let data = format!("{}", value["data"]);
-                self.handle_msg(&data)?;
-            }
-        } else if value["u"] != serde_json::Value::Null
-            && value["s"] != serde_json::Value::Null
-            && value["b"] != serde_json::Value::Null
-            && value["B"] != serde_json::Value::Null
-            && value["a"] != serde_json::Value::Null
-            && value["A"] != serde_json::Value::Null
-        {
-            let book_ticker: BookTickerEvent = from_str(msg)?;
-            (self.handler)(WebsocketEvent::BookTicker(book_ticker))?;
-        } else if msg.find(OUTBOUND_ACCOUNT_INFO) != None {
-            let account_update: AccountUpdateEvent = from_str(msg)?;
-            (self.handler)(WebsocketEvent::AccountUpdate(account_update))?;
-        } else if msg.find(EXECUTION_REPORT) != None {
-            let order_trade: OrderTradeEvent = from_str(msg)?;
-            (self.handler)(WebsocketEvent::OrderTrade(order_trade))?;
-        } else if msg.find(AGGREGATED_TRADE) != None {
-            let trade: AggrTradesEvent = from_str(msg)?;
-            (self.handler)(WebsocketEvent::AggrTrades(trade))?;
-        } else if msg.find(TRADE) != None {
-            let trade: TradeEvent = from_str(msg)?;
-            (self.handler)(WebsocketEvent::Trade(trade))?;
-        } else if msg.find(DAYTICKER) != None {
-            if self.subscription == "!ticker@arr" {
-                let trades: Vec<DayTickerEvent> = from_str(msg)?;
-                (self.handler)(WebsocketEvent::DayTickerAll(trades))?;
-            } else {
-                let trades: DayTickerEvent = from_str(msg)?;
-                (self.handler)(WebsocketEvent::DayTicker(trades))?;
-            }
-        } else if msg.find(KLINE) != None {
-            let kline: KlineEvent = from_str(msg)?;
-            (self.handler)(WebsocketEvent::Kline(kline))?;
-        } else if msg.find(PARTIAL_ORDERBOOK) != None {
-            let partial_orderbook: OrderBook = from_str(msg)?;
-            (self.handler)(WebsocketEvent::OrderBook(partial_orderbook))?;
-        } else if msg.find(DEPTH_ORDERBOOK) != None {
-            let depth_orderbook: DepthOrderBookEvent = from_str(msg)?;
-            (self.handler)(WebsocketEvent::DepthOrderBook(depth_orderbook))?;
+        if let Some(event) = parse_event(msg)? {
+            (self.handler)(event)?;
         }
         Ok(())
     }
 
+    // Returns `error` when there is no reconnect policy or it gives up
+    fn reconnect(&mut self, running: &AtomicBool, error: Error) -> Result<()> {
+        let reconnect_policy = match self.reconnect_policy.clone() {
+            Some(reconnect_policy) => reconnect_policy,
+            None => return Err(error),
+        };
+        self.socket = None;
+        (self.handler)(WebsocketEvent::Disconnected(error.to_string()))?;
+
+        let mut attempt = 1;
+        loop {
+            thread::sleep(reconnect_policy.backoff(attempt));
+            if !running.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let reconnected = match self.connect_url(self.url.clone()) {
+                Ok(()) => self.restore_subscriptions(),
+                Err(e) => Err(e),
+            };
+            match reconnected {
+                Ok(()) => return (self.handler)(WebsocketEvent::Reconnected),
+                Err(e) if !reconnect_policy.can_retry(attempt) => return Err(e),
+                Err(_) => attempt += 1,
+            }
+        }
+    }
+
     pub fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
         while running.load(Ordering::Relaxed) {
             if let Some(ref mut socket) = self.socket {
-                let message = socket.0.read_message()?;
+                let message = match socket.0.read_message() {
+                    Ok(message) => message,
+                    Err(e) => {
+                        self.reconnect(running, e.into())?;
+                        continue;
+                    }
+                };
                 match message {
                     Message::Text(msg) => {
                         if let Err(e) = self.handle_msg(&msg) {
@@ -176,7 +254,7 @@ impl<'a> WebSockets<'a> {
                     }
                     Message::Ping(_) | Message::Pong(_) | Message::Binary(_) => (),
                     Message::Close(e) => {
-                        bail!(format!("Disconnected {:?}", e));
+                        self.reconnect(running, format!("Disconnected {:?}", e).into())?;
                     }
                 }
             }
@@ -184,3 +262,655 @@ impl<'a> WebSockets<'a> {
         Ok(())
     }
 }
+
+// Decodes a message of a single or combined stream, None when it is not an event
+fn parse_event(msg: &str) -> Result<Option<WebsocketEvent>> {
+    let value: Value = from_str(msg)?;
+    if parse_response(&value).is_some() {
+        return Ok(None);
+    }
+    let (value, stream) = unwrap_stream(value);
+    Ok(Some(decode_event(value, stream.as_deref())?))
+}
+
+/// Takes the event out of a message of a combined stream, along with the name of its
+/// stream. Other messages are returned as they are.
+pub(crate) fn unwrap_stream(mut value: Value) -> (Value, Option<String>) {
+    match (
+        value.get("stream").and_then(Value::as_str),
+        value.get("data"),
+    ) {
+        (Some(stream), Some(_)) => {
+            let stream = stream.to_owned();
+            (value["data"].take(), Some(stream))
+        }
+        _ => (value, None),
+    }
+}
+
+// Dispatches on the `e` field. The book ticker and partial depth events don't have one,
+// so they are told apart by the name of their stream or their fields.
+fn decode_event(value: Value, stream: Option<&str>) -> Result<WebsocketEvent> {
+    let event_type = match &value {
+        Value::Array(events) => events.first().and_then(|event| event["e"].as_str()),
+        event => event["e"].as_str(),
+    };
+    let event = match event_type.map(str::to_owned).as_deref() {
+        Some(OUTBOUND_ACCOUNT_INFO) => WebsocketEvent::AccountUpdate(from_value(value)?),
+        Some(OUTBOUND_ACCOUNT_POSITION) => WebsocketEvent::AccountPosition(from_value(value)?),
+        Some(BALANCE_UPDATE) => WebsocketEvent::BalanceUpdate(from_value(value)?),
+        Some(EXECUTION_REPORT) => WebsocketEvent::OrderTrade(from_value(value)?),
+        Some(LIST_STATUS) => WebsocketEvent::ListStatus(from_value(value)?),
+        Some(LISTEN_KEY_EXPIRED) => WebsocketEvent::ListenKeyExpired(from_value(value)?),
+        Some(AGGREGATED_TRADE) => WebsocketEvent::AggrTrades(from_value(value)?),
+        Some(TRADE) => WebsocketEvent::Trade(from_value(value)?),
+        Some(DAYTICKER) if value.is_array() => WebsocketEvent::DayTickerAll(from_value(value)?),
+        Some(DAYTICKER) => WebsocketEvent::DayTicker(from_value(value)?),
+        Some(KLINE) => WebsocketEvent::Kline(from_value(value)?),
+        Some(DEPTH_ORDERBOOK) => WebsocketEvent::DepthOrderBook(from_value(value)?),
+        Some(_) => WebsocketEvent::Unknown(value),
+        None if value.get(PARTIAL_ORDERBOOK).is_some() => {
+            WebsocketEvent::OrderBook(from_value(value)?)
+        }
+        None if is_book_ticker(&value, stream) => WebsocketEvent::BookTicker(from_value(value)?),
+        None => WebsocketEvent::Unknown(value),
+    };
+    Ok(event)
+}
+
+fn is_book_ticker(value: &Value, stream: Option<&str>) -> bool {
+    match stream {
+        Some(stream) => stream.ends_with(BOOK_TICKER),
+        None => ["u", "s", "b", "B", "a", "A"]
+            .iter()
+            .all(|field| value.get(field).is_some()),
+    }
+}
+
+/// The async counterpart of `WebSockets`, built on tokio like `FuturesWebSockets`.
+///
+/// The handler receives every event along with a clone of `state`.
+pub struct AsyncWebSockets<Fut, S>
+where
+    Fut: Future<Output = Result<()>>,
+    S: Send + Sync + Clone,
+{
+    pub socket: Option<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)>,
+    handler: Box<dyn Fn(WebsocketEvent, S) -> Fut + 'static + Send + Sync>,
+    state: S,
+    url: String,
+    reconnect_policy: Option<ReconnectPolicy>,
+    subscriptions: Subscriptions,
+}
+
+impl<F, S> AsyncWebSockets<F, S>
+where
+    F: Future<Output = Result<()>>,
+    S: Send + Sync + Clone,
+{
+    pub fn new<Callback>(handler: Callback, state: S) -> AsyncWebSockets<F, S>
+    where
+        Callback: Fn(WebsocketEvent, S) -> F + 'static + Send + Sync,
+    {
+        AsyncWebSockets {
+            socket: None,
+            handler: Box::new(handler),
+            state,
+            url: String::new(),
+            reconnect_policy: None,
+            subscriptions: Subscriptions::default(),
+        }
+    }
+
+    /// Reconnect from `event_loop` when the connection is closed or fails, instead of
+    /// returning an error.
+    pub fn set_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = Some(reconnect_policy);
+        self
+    }
+
+    pub async fn connect(&mut self, subscription: &str) -> Result<()> {
+        self.subscriptions = Subscriptions::new(&[subscription])?;
+        let wss: String = format!("{}{}", WEBSOCKET_URL, subscription);
+        self.connect_url(wss).await
+    }
+
+    pub async fn connect_with_config(&mut self, subscription: &str, config: &Config) -> Result<()> {
+        self.subscriptions = Subscriptions::new(&[subscription])?;
+        let wss: String = format!("{}{}", &config.ws_endpoint, subscription);
+        self.connect_url(wss).await
+    }
+
+    pub async fn connect_multiple_streams(&mut self, endpoints: &[String]) -> Result<()> {
+        self.subscriptions = Subscriptions::new(endpoints)?;
+        let wss: String = format!("{}{}", WEBSOCKET_MULTI_STREAM, endpoints.join("/"));
+        self.connect_url(wss).await
+    }
+
+    pub async fn connect_streams(&mut self, streams: &[StreamName]) -> Result<()> {
+        self.connect_multiple_streams(&spot_names(streams)?).await
+    }
+
+    async fn connect_url(&mut self, wss: String) -> Result<()> {
+        self.socket = Some(connect_socket(&wss).await?);
+        self.url = wss;
+        Ok(())
+    }
+
+    pub async fn disconnect(&mut self) -> Result<()> {
+        if let Some(ref mut socket) = self.socket {
+            Ok(socket.0.close(None).await?)
+        } else {
+            bail!("Not able to close the connection");
+        }
+    }
+
+    /// Start listening to `streams` on the open connection.
+    pub async fn subscribe<Str: AsRef<str>>(&mut self, streams: &[Str]) -> Result<()> {
+        let request = self.subscriptions.subscribe(streams)?;
+        self.request(request).await?;
+        Ok(())
+    }
+
+    pub async fn unsubscribe<Str: AsRef<str>>(&mut self, streams: &[Str]) -> Result<()> {
+        let request = self.subscriptions.unsubscribe(streams);
+        self.request(request).await?;
+        Ok(())
+    }
+
+    /// The streams Binance reports the connection listening to.
+    pub async fn list_subscriptions(&mut self) -> Result<Vec<String>> {
+        let request = self.subscriptions.list_subscriptions();
+        Ok(serde_json::from_value(self.request(request).await?)?)
+    }
+
+    /// Wrap the events in `{"stream": ..., "data": ...}`, as combined streams do.
+    pub async fn set_combined(&mut self, combined: bool) -> Result<()> {
+        let request = self.subscriptions.set_combined(combined);
+        self.request(request).await?;
+        Ok(())
+    }
+
+    // Events received while waiting for the response are handled after it
+    async fn request(&mut self, request: Request) -> Result<serde_json::Value> {
+        tokio::time::sleep(self.subscriptions.delay()).await;
+        let mut received = Vec::new();
+        let result = match self.socket {
+            Some(ref mut socket) => {
+                send_request(
+                    &mut socket.0,
+                    &mut self.subscriptions,
+                    &request,
+                    &mut received,
+                )
+                .await
+            }
+            None => Err("Not connected".into()),
+        };
+        for msg in received {
+            if let Err(e) = self.handle_msg(&msg).await {
+                bail!(format!("Error on handling stream message: {}", e));
+            }
+        }
+        result
+    }
+
+    // Subscriptions made since connecting are lost with the connection
+    async fn restore_subscriptions(&mut self) -> Result<()> {
+        let (subscribe, unsubscribe) = self.subscriptions.changes();
+        if !subscribe.is_empty() {
+            self.subscribe(&subscribe).await?;
+        }
+        if !unsubscribe.is_empty() {
+            self.unsubscribe(&unsubscribe).await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_msg(&mut self, msg: &str) -> Result<()> {
+        if let Some(event) = parse_event(msg)? {
+            (self.handler)(event, self.state.clone()).await?;
+        }
+        Ok(())
+    }
+
+    // Returns `error` when there is no reconnect policy or it gives up
+    async fn reconnect(&mut self, running: &AtomicBool, error: Error) -> Result<()> {
+        let reconnect_policy = match self.reconnect_policy.clone() {
+            Some(reconnect_policy) => reconnect_policy,
+            None => return Err(error),
+        };
+        self.socket = None;
+        let disconnected = WebsocketEvent::Disconnected(error.to_string());
+        (self.handler)(disconnected, self.state.clone()).await?;
+
+        let mut attempt = 1;
+        loop {
+            tokio::time::sleep(reconnect_policy.backoff(attempt)).await;
+            if !running.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let reconnected = match self.connect_url(self.url.clone()).await {
+                Ok(()) => self.restore_subscriptions().await,
+                Err(e) => Err(e),
+            };
+            match reconnected {
+                Ok(()) => {
+                    return (self.handler)(WebsocketEvent::Reconnected, self.state.clone()).await
+                }
+                Err(e) if !reconnect_policy.can_retry(attempt) => return Err(e),
+                Err(_) => attempt += 1,
+            }
+        }
+    }
+
+    pub async fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
+        while running.load(Ordering::Relaxed) {
+            if let Some(ref mut socket) = self.socket {
+                let message = match socket.0.next().await {
+                    Some(Ok(message)) => message,
+                    Some(Err(e)) => {
+                        self.reconnect(running, e.into()).await?;
+                        continue;
+                    }
+                    None => {
+                        self.reconnect(running, "Disconnected".into()).await?;
+                        continue;
+                    }
+                };
+                match message {
+                    Message::Text(msg) => {
+                        if let Err(e) = self.handle_msg(&msg).await {
+                            bail!(format!("Error on handling stream message: {}", e));
+                        }
+                    }
+                    Message::Ping(_) | Message::Pong(_) | Message::Binary(_) => (),
+                    Message::Close(e) => {
+                        let error = format!("Disconnected {:?}", e).into();
+                        self.reconnect(running, error).await?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A connection yielding the events of its streams, as an alternative to the
+/// `AsyncWebSockets` callback and `event_loop`.
+///
+/// A message that can't be decoded yields an error and the stream carries on. It ends
+/// after the connection is closed or fails. Dropping it closes the connection.
+pub struct EventStream {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    subscriptions: Subscriptions,
+    // Messages received while waiting for a response
+    received: VecDeque<String>,
+    closed: bool,
+}
+
+impl EventStream {
+    pub async fn connect(subscription: &str) -> Result<EventStream> {
+        let wss: String = format!("{}{}", WEBSOCKET_URL, subscription);
+        EventStream::connect_url(&wss, &[subscription]).await
+    }
+
+    pub async fn connect_with_config(subscription: &str, config: &Config) -> Result<EventStream> {
+        let wss: String = format!("{}{}", &config.ws_endpoint, subscription);
+        EventStream::connect_url(&wss, &[subscription]).await
+    }
+
+    pub async fn connect_multiple_streams(endpoints: &[String]) -> Result<EventStream> {
+        let wss: String = format!("{}{}", WEBSOCKET_MULTI_STREAM, endpoints.join("/"));
+        EventStream::connect_url(&wss, endpoints).await
+    }
+
+    pub async fn connect_streams(streams: &[StreamName]) -> Result<EventStream> {
+        EventStream::connect_multiple_streams(&spot_names(streams)?).await
+    }
+
+    async fn connect_url<S: AsRef<str>>(wss: &str, streams: &[S]) -> Result<EventStream> {
+        let subscriptions = Subscriptions::new(streams)?;
+        let (socket, _) = connect_socket(wss).await?;
+        Ok(EventStream {
+            socket,
+            subscriptions,
+            received: VecDeque::new(),
+            closed: false,
+        })
+    }
+
+    /// Start listening to `streams` on the open connection.
+    pub async fn subscribe<S: AsRef<str>>(&mut self, streams: &[S]) -> Result<()> {
+        let request = self.subscriptions.subscribe(streams)?;
+        self.request(request).await?;
+        Ok(())
+    }
+
+    pub async fn unsubscribe<S: AsRef<str>>(&mut self, streams: &[S]) -> Result<()> {
+        let request = self.subscriptions.unsubscribe(streams);
+        self.request(request).await?;
+        Ok(())
+    }
+
+    /// The streams Binance reports the connection listening to.
+    pub async fn list_subscriptions(&mut self) -> Result<Vec<String>> {
+        let request = self.subscriptions.list_subscriptions();
+        Ok(serde_json::from_value(self.request(request).await?)?)
+    }
+
+    /// Wrap the events in `{"stream": ..., "data": ...}`, as combined streams do.
+    pub async fn set_combined(&mut self, combined: bool) -> Result<()> {
+        let request = self.subscriptions.set_combined(combined);
+        self.request(request).await?;
+        Ok(())
+    }
+
+    // Events received while waiting for the response are yielded after it
+    async fn request(&mut self, request: Request) -> Result<serde_json::Value> {
+        tokio::time::sleep(self.subscriptions.delay()).await;
+        let mut received = Vec::new();
+        let result = send_request(
+            &mut self.socket,
+            &mut self.subscriptions,
+            &request,
+            &mut received,
+        )
+        .await;
+        self.received.extend(received);
+        result
+    }
+
+    /// Close the connection with a close frame rather than by dropping it.
+    pub async fn close(&mut self) -> Result<()> {
+        self.closed = true;
+        Ok(self.socket.close(None).await?)
+    }
+
+    // Whether the connection was closed or failed, after which the stream ends
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<WebsocketEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        while let Some(msg) = this.received.pop_front() {
+            if let Some(event) = parse_event(&msg).transpose() {
+                return Poll::Ready(Some(event));
+            }
+        }
+        while !this.closed {
+            let message = match ready!(this.socket.poll_next_unpin(cx)) {
+                Some(Ok(message)) => message,
+                Some(Err(e)) => {
+                    this.closed = true;
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+                None => break,
+            };
+            match message {
+                Message::Text(msg) => {
+                    if let Some(event) = parse_event(&msg).transpose() {
+                        return Poll::Ready(Some(event));
+                    }
+                }
+                Message::Ping(_) | Message::Pong(_) | Message::Binary(_) => (),
+                Message::Close(e) => {
+                    this.closed = true;
+                    return Poll::Ready(Some(Err(format!("Disconnected {:?}", e).into())));
+                }
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+fn spot_names(streams: &[StreamName]) -> Result<Vec<String>> {
+    streams.iter().map(StreamName::spot_name).collect()
+}
+
+async fn connect_socket(
+    wss: &str,
+) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> {
+    let url = Url::parse(wss)?;
+
+    match connect_async(url).await {
+        Ok(answer) => Ok(answer),
+        Err(e) => {
+            bail!(format!("Error during handshake {}", e));
+        }
+    }
+}
+
+/// Streams a single connection can listen to.
+pub const MAX_STREAMS: usize = 1024;
+/// Messages a connection can send each second, including SUBSCRIBE and UNSUBSCRIBE.
+pub const MAX_MESSAGES_PER_SECOND: usize = 5;
+
+// A SUBSCRIBE, UNSUBSCRIBE, LIST_SUBSCRIPTIONS or SET_PROPERTY message
+pub(crate) struct Request {
+    id: u64,
+    method: &'static str,
+    params: Vec<serde_json::Value>,
+    streams: Vec<String>,
+}
+
+impl Request {
+    fn to_message(&self) -> Message {
+        let mut request = serde_json::json!({ "method": self.method, "id": self.id });
+        if !self.params.is_empty() {
+            request["params"] = self.params.clone().into();
+        }
+        Message::Text(request.to_string())
+    }
+}
+
+// The streams of a connection and the requests changing them
+#[derive(Default)]
+pub(crate) struct Subscriptions {
+    last_id: u64,
+    // Streams named in the connection URL, subscribed again by reconnecting to it
+    url_streams: BTreeSet<String>,
+    streams: BTreeSet<String>,
+    // When the last messages were sent, or will be
+    sent: VecDeque<Instant>,
+}
+
+impl Subscriptions {
+    pub(crate) fn new<S: AsRef<str>>(url_streams: &[S]) -> Result<Self> {
+        let url_streams: BTreeSet<String> = url_streams
+            .iter()
+            .map(|stream| stream.as_ref().to_string())
+            .collect();
+        if url_streams.len() > MAX_STREAMS {
+            bail!(format!(
+                "A connection can listen to at most {} streams",
+                MAX_STREAMS
+            ));
+        }
+        Ok(Subscriptions {
+            streams: url_streams.clone(),
+            url_streams,
+            ..Subscriptions::default()
+        })
+    }
+
+    pub(crate) fn subscribe<S: AsRef<str>>(&mut self, streams: &[S]) -> Result<Request> {
+        let streams: Vec<String> = streams
+            .iter()
+            .map(|stream| stream.as_ref().to_string())
+            .collect();
+        let new_streams = streams
+            .iter()
+            .filter(|stream| !self.streams.contains(*stream))
+            .count();
+        if self.streams.len() + new_streams > MAX_STREAMS {
+            bail!(format!(
+                "A connection can listen to at most {} streams",
+                MAX_STREAMS
+            ));
+        }
+        Ok(self.request("SUBSCRIBE", streams))
+    }
+
+    pub(crate) fn unsubscribe<S: AsRef<str>>(&mut self, streams: &[S]) -> Request {
+        let streams = streams
+            .iter()
+            .map(|stream| stream.as_ref().to_string())
+            .collect();
+        self.request("UNSUBSCRIBE", streams)
+    }
+
+    pub(crate) fn list_subscriptions(&mut self) -> Request {
+        self.request("LIST_SUBSCRIPTIONS", vec![])
+    }
+
+    pub(crate) fn set_combined(&mut self, combined: bool) -> Request {
+        self.last_id += 1;
+        Request {
+            id: self.last_id,
+            method: "SET_PROPERTY",
+            params: vec!["combined".into(), combined.into()],
+            streams: vec![],
+        }
+    }
+
+    fn request(&mut self, method: &'static str, streams: Vec<String>) -> Request {
+        self.last_id += 1;
+        Request {
+            id: self.last_id,
+            method,
+            params: streams
+                .iter()
+                .map(|stream| stream.as_str().into())
+                .collect(),
+            streams,
+        }
+    }
+
+    fn confirm(&mut self, request: &Request) {
+        match request.method {
+            "SUBSCRIBE" => self.streams.extend(request.streams.iter().cloned()),
+            "UNSUBSCRIBE" => {
+                for stream in &request.streams {
+                    self.streams.remove(stream);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // How long to wait before sending the next message
+    pub(crate) fn delay(&mut self) -> Duration {
+        let now = Instant::now();
+        let window = Duration::from_secs(1);
+        while matches!(self.sent.front(), Some(sent) if now.saturating_duration_since(*sent) >= window)
+        {
+            self.sent.pop_front();
+        }
+
+        let send_at = if self.sent.len() >= MAX_MESSAGES_PER_SECOND {
+            self.sent.pop_front().map_or(now, |sent| sent + window)
+        } else {
+            now
+        };
+        self.sent.push_back(send_at);
+        send_at.saturating_duration_since(now)
+    }
+
+    // Streams to subscribe to and unsubscribe from after reconnecting to the URL
+    pub(crate) fn changes(&self) -> (Vec<String>, Vec<String>) {
+        (
+            self.streams
+                .difference(&self.url_streams)
+                .cloned()
+                .collect(),
+            self.url_streams
+                .difference(&self.streams)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+// The id and result of a response to a `Request`, None for other messages
+pub(crate) fn parse_response(
+    value: &serde_json::Value,
+) -> Option<(u64, Result<serde_json::Value>)> {
+    let id = value.get("id")?.as_u64()?;
+    if let Some(result) = value.get("result") {
+        return Some((id, Ok(result.clone())));
+    }
+
+    let error = value.get("error").unwrap_or(value);
+    match serde_json::from_value::<BinanceContentError>(error.clone()) {
+        Ok(error) => Some((id, Err(ErrorKind::BinanceError(error).into()))),
+        Err(_) => None,
+    }
+}
+
+// Sends `request` and reads up to its response, keeping the other messages in `received`
+pub(crate) async fn send_request(
+    socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>, subscriptions: &mut Subscriptions,
+    request: &Request, received: &mut Vec<String>,
+) -> Result<serde_json::Value> {
+    socket.send(request.to_message()).await?;
+    loop {
+        let message = match socket.next().await {
+            Some(message) => message?,
+            None => bail!("Disconnected"),
+        };
+        match message {
+            Message::Text(msg) => {
+                if let Some(result) = response_to(request, &msg, received)? {
+                    subscriptions.confirm(request);
+                    return Ok(result);
+                }
+            }
+            Message::Ping(_) | Message::Pong(_) | Message::Binary(_) => (),
+            Message::Close(e) => {
+                bail!(format!("Disconnected {:?}", e));
+            }
+        }
+    }
+}
+
+// The blocking counterpart of `send_request`
+fn send_request_blocking(
+    socket: &mut WebSocket<AutoStream>, subscriptions: &mut Subscriptions, request: &Request,
+    received: &mut Vec<String>,
+) -> Result<serde_json::Value> {
+    socket.write_message(request.to_message())?;
+    loop {
+        match socket.read_message()? {
+            Message::Text(msg) => {
+                if let Some(result) = response_to(request, &msg, received)? {
+                    subscriptions.confirm(request);
+                    return Ok(result);
+                }
+            }
+            Message::Ping(_) | Message::Pong(_) | Message::Binary(_) => (),
+            Message::Close(e) => {
+                bail!(format!("Disconnected {:?}", e));
+            }
+        }
+    }
+}
+
+fn response_to(
+    request: &Request, msg: &str, received: &mut Vec<String>,
+) -> Result<Option<serde_json::Value>> {
+    let value: serde_json::Value = serde_json::from_str(msg)?;
+    match parse_response(&value) {
+        Some((id, result)) if id == request.id => Ok(Some(result?)),
+        Some(_) => Ok(None),
+        None => {
+            received.push(msg.to_string());
+            Ok(None)
+        }
+    }
+}
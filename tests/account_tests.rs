@@ -1,12 +1,19 @@
 use binance::api::*;
 use binance::config::*;
-use binance::account::*;
+use binance::blocking::account::*;
+use binance::model::{
+    Amount, CancelReplaceOutcome, CancelReplaceStatus, ExecutionType, OrderResponse, OrderStatus,
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use mockito::{mock, Matcher};
 
+    fn amount(value: &str) -> Amount {
+        value.parse().unwrap()
+    }
+
     #[test]
     fn test_order_market_buy_using_quote_quantity() {
         let mock_exchange_info = mock("POST", "/api/v3/order")
@@ -20,7 +27,7 @@ mod tests {
             .set_recv_window(1234);
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
-        match account.market_buy_using_quote_quantity("BNBBTC", 0.002) {
+        match account.market_buy_using_quote_quantity("BNBBTC", amount("0.002")) {
             Ok(answer) => {
                 assert!(answer.order_id == 1);
             }
@@ -29,4 +36,393 @@ mod tests {
 
         mock_exchange_info.assert();
     }
+
+    #[test]
+    fn test_order_oco_sell() {
+        let mock_oco = mock("POST", "/api/v3/orderList/oco")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("abovePrice=0.036435&aboveType=LIMIT_MAKER&belowStopPrice=0.960664&belowType=STOP_LOSS&quantity=0.624363&recvWindow=1234&side=SELL&symbol=LTCBTC&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/order_oco_sell.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let _ = env_logger::try_init();
+        match account.oco_sell(
+            "LTCBTC",
+            amount("0.624363"),
+            amount("0.036435"),
+            amount("0.960664"),
+            None,
+        ) {
+            Ok(answer) => {
+                assert_eq!(answer.contingency_type, "OCO");
+                assert_eq!(answer.orders.len(), 2);
+                assert_eq!(answer.order_reports.len(), 2);
+                assert_eq!(answer.order_reports[0].stop_price, Some(amount("0.960664")));
+                assert_eq!(answer.order_reports[1].type_name, OrderType::LimitMaker);
+            }
+            Err(e) => panic!("Error: {}", e),
+        }
+
+        mock_oco.assert();
+    }
+
+    #[test]
+    fn test_order_oco_buy() {
+        let mock_oco = mock("POST", "/api/v3/orderList/oco")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("abovePrice=0.0121&aboveStopPrice=0.012&aboveTimeInForce=GTC&aboveType=STOP_LOSS_LIMIT&belowPrice=0.01&belowType=LIMIT_MAKER&quantity=10&recvWindow=1234&side=BUY&symbol=LTCBTC&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/order_oco_sell.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        account
+            .oco_buy(
+                "LTCBTC",
+                10,
+                amount("0.01"),
+                amount("0.012"),
+                Some(amount("0.0121")),
+            )
+            .unwrap();
+
+        mock_oco.assert();
+    }
+
+    #[test]
+    fn test_all_order_lists() {
+        let mock_all_order_lists = mock("GET", "/api/v3/allOrderList")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "fromId=29&limit=10&recvWindow=1234&timestamp=\\d+&signature=.*".into(),
+            ))
+            .with_body_from_file("tests/mocks/account/open_order_lists.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order_lists = account.get_all_order_lists(29, None, None, 10).unwrap();
+        assert_eq!(order_lists[0].order_list_id, 31);
+
+        mock_all_order_lists.assert();
+    }
+
+    #[test]
+    fn test_open_order_lists() {
+        let mock_open_order_lists = mock("GET", "/api/v3/openOrderList")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "recvWindow=1234&timestamp=\\d+&signature=.*".into(),
+            ))
+            .with_body_from_file("tests/mocks/account/open_order_lists.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        match account.get_open_order_lists() {
+            Ok(answer) => {
+                assert_eq!(answer.len(), 1);
+                assert_eq!(answer[0].order_list_id, 31);
+                assert!(answer[0].order_reports.is_empty());
+            }
+            Err(e) => panic!("Error: {}", e),
+        }
+
+        mock_open_order_lists.assert();
+    }
+
+    #[test]
+    fn test_place_order_with_result_response() {
+        let mock_order = mock("POST", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("newClientOrderId=my-order-1&newOrderRespType=RESULT&price=1&quantity=10&recvWindow=1234&selfTradePreventionMode=NONE&side=BUY&symbol=BTCUSDT&timeInForce=GTC&timestamp=\\d+&type=LIMIT&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/order_limit_buy_result.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order = OrderRequest::new("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+            .set_time_in_force(TimeInForce::GTC)
+            .set_quantity(amount("10"))
+            .set_price(amount("1"))
+            .set_new_client_order_id("my-order-1")
+            .set_new_order_resp_type(OrderResponseType::Result)
+            .set_self_trade_prevention_mode(SelfTradePreventionMode::None);
+        match account.place_order(order) {
+            Ok(OrderResponse::Result(answer)) => {
+                assert_eq!(answer.order_id, 28);
+                assert_eq!(answer.client_order_id, "my-order-1");
+                assert_eq!(answer.status, OrderStatus::Filled);
+            }
+            Ok(answer) => panic!("Unexpected response: {:?}", answer),
+            Err(e) => panic!("Error: {}", e),
+        }
+
+        mock_order.assert();
+    }
+
+    #[test]
+    fn test_test_order() {
+        let mock_test_order = mock("POST", "/api/v3/order/test")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("quantity=1&recvWindow=1234&side=SELL&stopPrice=0.5&symbol=BNBBTC&timestamp=\\d+&type=STOP_LOSS&signature=.*".into()))
+            .with_body("{}")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order = OrderRequest::new("BNBBTC", OrderSide::Sell, OrderType::StopLoss)
+            .set_quantity(amount("1"))
+            .set_stop_price(amount("0.5"));
+        account.test_order(order).unwrap();
+
+        mock_test_order.assert();
+    }
+
+    #[test]
+    fn test_order_request_validation() {
+        let limit =
+            OrderRequest::new("BNBBTC", OrderSide::Buy, OrderType::Limit).set_quantity(amount("1"));
+        assert!(limit.clone().validate().is_err());
+        assert!(limit.clone().set_price(amount("0.1")).validate().is_err());
+        assert!(limit
+            .set_price(amount("0.1"))
+            .set_time_in_force(TimeInForce::GTC)
+            .validate()
+            .is_ok());
+
+        let market = OrderRequest::new("BNBBTC", OrderSide::Buy, OrderType::Market);
+        assert!(market.clone().validate().is_err());
+        assert!(market
+            .clone()
+            .set_quote_order_qty(amount("0.1"))
+            .validate()
+            .is_ok());
+        assert!(market
+            .clone()
+            .set_quote_order_qty(amount("0.1"))
+            .set_price(amount("0.1"))
+            .validate()
+            .is_err());
+        assert!(market
+            .clone()
+            .set_quote_order_qty(amount("0.1"))
+            .set_time_in_force(TimeInForce::IOC)
+            .validate()
+            .is_err());
+        assert!(market
+            .set_quantity(amount("1"))
+            .set_quote_order_qty(amount("0.1"))
+            .validate()
+            .is_err());
+
+        let stop_loss = OrderRequest::new("BNBBTC", OrderSide::Sell, OrderType::StopLoss)
+            .set_quantity(amount("1"))
+            .set_stop_price(amount("0.19"));
+        assert!(stop_loss.clone().validate().is_ok());
+        assert!(stop_loss
+            .clone()
+            .set_price(amount("0.18"))
+            .validate()
+            .is_err());
+        assert!(stop_loss
+            .set_time_in_force(TimeInForce::GTC)
+            .validate()
+            .is_err());
+
+        let take_profit_limit =
+            OrderRequest::new("BNBBTC", OrderSide::Sell, OrderType::TakeProfitLimit)
+                .set_quantity(amount("1"))
+                .set_price(amount("0.2"))
+                .set_time_in_force(TimeInForce::GTC);
+        assert!(take_profit_limit.clone().validate().is_err());
+        assert!(take_profit_limit
+            .clone()
+            .set_trailing_delta(100)
+            .validate()
+            .is_ok());
+        assert!(take_profit_limit
+            .set_stop_price(amount("0.19"))
+            .set_iceberg_qty(amount("0.1"))
+            .validate()
+            .is_ok());
+
+        let limit_maker = OrderRequest::new("BNBBTC", OrderSide::Sell, OrderType::LimitMaker)
+            .set_quantity(amount("1"))
+            .set_price(amount("0.2"));
+        assert!(limit_maker.clone().validate().is_ok());
+        assert!(limit_maker
+            .clone()
+            .set_stop_price(amount("0.1"))
+            .validate()
+            .is_err());
+        assert!(limit_maker
+            .set_time_in_force(TimeInForce::GTC)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_get_all_orders() {
+        let mock_all_orders = mock("GET", "/api/v3/allOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("endTime=1499827400000&limit=10&recvWindow=1234&startTime=1499827300000&symbol=LTCBTC&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/all_orders.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        match account.get_all_orders("LTCBTC", None, 1499827300000, 1499827400000, 10) {
+            Ok(answer) => {
+                assert_eq!(answer.len(), 1);
+                assert_eq!(answer[0].client_order_id, "myOrder1");
+                assert_eq!(answer[0].status, OrderStatus::New);
+                assert_eq!(answer[0].time_in_force, TimeInForce::GTC);
+                assert_eq!(answer[0].type_name, OrderType::Limit);
+                assert_eq!(answer[0].side, OrderSide::Buy);
+            }
+            Err(e) => panic!("Error: {}", e),
+        }
+
+        mock_all_orders.assert();
+    }
+
+    #[test]
+    fn test_order_enums() {
+        let status: OrderStatus = serde_json::from_str("\"PARTIALLY_FILLED\"").unwrap();
+        assert_eq!(status, OrderStatus::PartiallyFilled);
+        assert_eq!(
+            serde_json::to_string(&status).unwrap(),
+            "\"PARTIALLY_FILLED\""
+        );
+
+        let execution_type: ExecutionType = serde_json::from_str("\"NEW_STATE\"").unwrap();
+        assert_eq!(execution_type, ExecutionType::Other("NEW_STATE".into()));
+        assert_eq!(
+            serde_json::to_string(&execution_type).unwrap(),
+            "\"NEW_STATE\""
+        );
+
+        assert_eq!(OrderType::from("STOP_LOSS_LIMIT"), OrderType::StopLossLimit);
+        assert_eq!(String::from(TimeInForce::GTX), "GTX");
+    }
+
+    #[test]
+    fn test_cancel_order_with_client_id() {
+        let mock_cancel = mock("DELETE", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("newClientOrderId=cancelMyOrder1&origClientOrderId=myOrder1&recvWindow=1234&symbol=LTCBTC&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/cancel_order_with_client_id.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        match account.cancel_order_with_client_id("LTCBTC", "myOrder1", Some("cancelMyOrder1")) {
+            Ok(answer) => {
+                assert_eq!(answer.orig_client_order_id, "myOrder1");
+                assert_eq!(answer.client_order_id, "cancelMyOrder1");
+            }
+            Err(e) => panic!("Error: {}", e),
+        }
+
+        mock_cancel.assert();
+    }
+
+    #[test]
+    fn test_cancel_replace_order() {
+        let mock_cancel_replace = mock("POST", "/api/v3/order/cancelReplace")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("cancelOrderId=9&cancelReplaceMode=STOP_ON_FAILURE&newOrderRespType=ACK&price=0.01&quantity=0.0001&recvWindow=1234&side=SELL&symbol=BTCUSDT&timeInForce=GTC&timestamp=\\d+&type=LIMIT&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/cancel_replace.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order = OrderRequest::new("BTCUSDT", OrderSide::Sell, OrderType::Limit)
+            .set_time_in_force(TimeInForce::GTC)
+            .set_quantity(amount("0.0001"))
+            .set_price(amount("0.01"))
+            .set_new_order_resp_type(OrderResponseType::Ack);
+        let answer = account
+            .cancel_replace_order(9, order, CancelReplaceMode::StopOnFailure)
+            .unwrap();
+
+        mock_cancel_replace.assert();
+
+        assert!(answer.is_success());
+        match answer.cancel_response {
+            CancelReplaceOutcome::Success(canceled) => assert_eq!(canceled.order_id, 9),
+            CancelReplaceOutcome::Failure(e) => panic!("Unexpected error: {:?}", e),
+        }
+        match answer.new_order_response {
+            Some(CancelReplaceOutcome::Success(OrderResponse::Ack(order))) => {
+                assert_eq!(order.order_id, 10);
+                assert_eq!(order.client_order_id, "wOceeeOzNORyLiQfw7jd8S");
+            }
+            other => panic!("Unexpected response: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cancel_replace_order_partially_failed() {
+        let mock_cancel_replace = mock("POST", "/api/v3/order/cancelReplace")
+            .with_status(409)
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("cancelOrigClientOrderId=my-order-1&cancelReplaceMode=ALLOW_FAILURE&quantity=0.0001&recvWindow=1234&side=BUY&symbol=BTCUSDT&timestamp=\\d+&type=MARKET&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/cancel_replace_partially_failed.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order = OrderRequest::new("BTCUSDT", OrderSide::Buy, OrderType::Market)
+            .set_quantity(amount("0.0001"));
+        let answer = account
+            .cancel_replace_order_with_client_id(
+                "my-order-1",
+                order,
+                CancelReplaceMode::AllowFailure,
+            )
+            .unwrap();
+
+        mock_cancel_replace.assert();
+
+        assert!(!answer.is_success());
+        assert_eq!(answer.cancel_result, CancelReplaceStatus::Failure);
+        assert_eq!(answer.new_order_result, CancelReplaceStatus::Success);
+        match answer.cancel_response {
+            CancelReplaceOutcome::Failure(e) => {
+                assert_eq!(e.code, -2011);
+                assert_eq!(e.msg, "Unknown order sent.");
+            }
+            CancelReplaceOutcome::Success(canceled) => {
+                panic!("Unexpected response: {:?}", canceled)
+            }
+        }
+        match answer.new_order_response {
+            Some(CancelReplaceOutcome::Success(order)) => assert_eq!(order.order_id(), 11),
+            other => panic!("Unexpected response: {:?}", other),
+        }
+    }
 }
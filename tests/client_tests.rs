@@ -0,0 +1,398 @@
+use binance::api::*;
+use binance::config::*;
+use binance::blocking::account::*;
+use binance::blocking::general::*;
+use binance::blocking::market::*;
+use binance::errors::{BinanceErrorCode, ErrorKind};
+use binance::model::{Amount, RateLimit};
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+
+    fn amount(value: &str) -> Amount {
+        value.parse().unwrap()
+    }
+
+    fn retry_config() -> Config {
+        let retry_policy = RetryPolicy::default()
+            .set_max_attempts(3)
+            .set_backoff(Duration::from_millis(1), Duration::from_millis(5));
+
+        Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_retry_policy(retry_policy)
+    }
+
+    #[test]
+    fn get_is_retried_until_max_attempts() {
+        let mock_depth = mock("GET", "/api/v3/depth")
+            .match_query(Matcher::UrlEncoded("symbol".into(), "LTCBTC".into()))
+            .with_status(503)
+            .expect(3)
+            .create();
+
+        let market: Market = Binance::new_with_config(None, None, &retry_config());
+        assert!(market.get_depth("LTCBTC").is_err());
+
+        mock_depth.assert();
+    }
+
+    #[test]
+    fn order_without_client_order_id_is_not_retried() {
+        let mock_order = mock("POST", "/api/v3/order")
+            .match_query(Matcher::Regex("symbol=ETHBTC".into()))
+            .with_status(503)
+            .expect(1)
+            .create();
+
+        let config = retry_config();
+        let config = config
+            .clone()
+            .set_retry_policy(config.retry_policy.set_retry_orders(true));
+        let account: Account = Binance::new_with_config(None, None, &config);
+        assert!(account.market_buy("ETHBTC", 1).is_err());
+
+        mock_order.assert();
+    }
+
+    #[test]
+    fn order_list_with_client_id_is_retried() {
+        let mock_oco = mock("POST", "/api/v3/orderList/oco")
+            .match_query(Matcher::Regex("listClientOrderId=my-list-1".into()))
+            .with_status(503)
+            .expect(3)
+            .create();
+
+        let config = retry_config();
+        let config = config
+            .clone()
+            .set_retry_policy(config.retry_policy.set_retry_orders(true));
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let request = "aboveType=LIMIT_MAKER&abovePrice=0.04&belowType=STOP_LOSS\
+                       &belowStopPrice=0.03&listClientOrderId=my-list-1&quantity=1\
+                       &side=SELL&symbol=LTCBTC";
+        let result = account
+            .client
+            .post_signed::<serde_json::Value>(API::Spot(Spot::Oco), request.into());
+        assert!(result.is_err());
+
+        mock_oco.assert();
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max() {
+        let retry_policy = RetryPolicy::default()
+            .set_jitter(false)
+            .set_backoff(Duration::from_millis(100), Duration::from_millis(350));
+
+        assert_eq!(retry_policy.backoff(1, None), Duration::from_millis(100));
+        assert_eq!(retry_policy.backoff(2, None), Duration::from_millis(200));
+        assert_eq!(retry_policy.backoff(3, None), Duration::from_millis(350));
+        assert_eq!(
+            retry_policy.backoff(3, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn used_weight_is_read_from_headers() {
+        let mock_price = mock("GET", "/api/v3/ticker/price")
+            .match_query(Matcher::UrlEncoded("symbol".into(), "LTCBTC".into()))
+            .with_header("content-type", "application/json")
+            .with_header("x-mbx-used-weight", "7")
+            .with_header("x-mbx-used-weight-1m", "7")
+            .with_body(r#"{"symbol":"LTCBTC","price":"4.00000200"}"#)
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config);
+        market.get_price("LTCBTC").unwrap();
+
+        mock_price.assert();
+
+        let used_weight = market.client.rate_limits().used_weight();
+        assert_eq!(used_weight.len(), 1);
+        assert_eq!(used_weight[0].interval, "MINUTE");
+        assert_eq!(used_weight[0].interval_num, 1);
+        assert_eq!(used_weight[0].count, 7);
+        assert_eq!(used_weight[0].limit, None);
+    }
+
+    #[test]
+    fn limiter_rejects_requests_over_the_limit() {
+        let mock_avg_price = mock("GET", "/api/v3/avgPrice")
+            .match_query(Matcher::UrlEncoded("symbol".into(), "LTCBTC".into()))
+            .with_header("content-type", "application/json")
+            .with_header("x-mbx-used-weight-1d", "1200")
+            .with_body(r#"{"mins":5,"price":"9.35751834"}"#)
+            .expect(1)
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_rate_limiter(RateLimiter::Reject);
+        let market: Market = Binance::new_with_config(None, None, &config);
+        market.client.rate_limits().seed(&[RateLimit {
+            rate_limit_type: "REQUEST_WEIGHT".into(),
+            interval: "DAY".into(),
+            interval_num: 1,
+            limit: 1200,
+        }]);
+
+        market.get_average_price("LTCBTC").unwrap();
+        match market.get_average_price("LTCBTC") {
+            Err(e) => match e.0 {
+                ErrorKind::RateLimitExceeded(retry_in) => {
+                    assert!(retry_in <= Duration::from_secs(86400))
+                }
+                _ => panic!("Unexpected error: {}", e),
+            },
+            Ok(_) => panic!("Request should have been rejected"),
+        }
+
+        mock_avg_price.assert();
+    }
+
+    #[test]
+    fn limiter_reserves_weight_before_sending() {
+        // avgPrice weighs 2, so a second request does not fit a limit of 3 even
+        // though no response has reported any usage yet
+        let mock_avg_price = mock("GET", "/api/v3/avgPrice")
+            .match_query(Matcher::UrlEncoded("symbol".into(), "BNBBTC".into()))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"mins":5,"price":"9.35751834"}"#)
+            .expect(1)
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_rate_limiter(RateLimiter::Reject);
+        let market: Market = Binance::new_with_config(None, None, &config);
+        market.client.rate_limits().seed(&[RateLimit {
+            rate_limit_type: "REQUEST_WEIGHT".into(),
+            interval: "DAY".into(),
+            interval_num: 1,
+            limit: 3,
+        }]);
+
+        market.get_average_price("BNBBTC").unwrap();
+        assert_eq!(market.client.rate_limits().used_weight()[0].count, 2);
+        match market.get_average_price("BNBBTC") {
+            Err(e) => match e.0 {
+                ErrorKind::RateLimitExceeded(_) => {}
+                _ => panic!("Unexpected error: {}", e),
+            },
+            Ok(_) => panic!("Request should have been rejected"),
+        }
+
+        mock_avg_price.assert();
+    }
+
+    #[test]
+    fn full_order_limit_only_rejects_new_orders() {
+        let mock_cancel = mock("DELETE", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_header("x-mbx-order-count-1d", "1")
+            .match_query(Matcher::Regex("origClientOrderId=myOrder1".into()))
+            .with_body_from_file("tests/mocks/account/cancel_order_with_client_id.json")
+            .expect(2)
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_rate_limiter(RateLimiter::Reject);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        account.client.rate_limits().seed(&[RateLimit {
+            rate_limit_type: "ORDERS".into(),
+            interval: "DAY".into(),
+            interval_num: 1,
+            limit: 1,
+        }]);
+
+        for _ in 0..2 {
+            account
+                .cancel_order_with_client_id("LTCBTC", "myOrder1", None)
+                .unwrap();
+        }
+        match account.limit_buy("LTCBTC", 1, Default::default()) {
+            Err(e) => match e.0 {
+                ErrorKind::RateLimitExceeded(_) => {}
+                _ => panic!("Unexpected error: {}", e),
+            },
+            Ok(_) => panic!("Order should have been rejected"),
+        }
+
+        mock_cancel.assert();
+    }
+
+    #[test]
+    fn time_is_resynced_after_the_interval() {
+        let mock_time = mock("GET", "/api/v3/time")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"serverTime":4102444800000}"#)
+            .expect(3)
+            .create();
+        let mock_open_orders = mock("GET", "/api/v3/openOrders")
+            .match_query(Matcher::Regex("timestamp=4102444".into()))
+            .with_header("content-type", "application/json")
+            .with_body("[]")
+            .expect(4)
+            .create();
+
+        // Synced before the first signed request and not again within the interval
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        assert_eq!(config.time_sync_interval, Some(DEFAULT_TIME_SYNC_INTERVAL));
+        let account: Account = Binance::new_with_config(None, None, &config);
+        account.get_all_open_orders().unwrap();
+        account.get_all_open_orders().unwrap();
+        assert!(account.client.time_sync().offset().unwrap() > 0);
+
+        // Measured again on the first signed request after the interval
+        let config = config.set_time_sync_interval(Duration::from_millis(20));
+        let account: Account = Binance::new_with_config(None, None, &config);
+        account.get_all_open_orders().unwrap();
+        std::thread::sleep(Duration::from_millis(30));
+        account.get_all_open_orders().unwrap();
+
+        mock_time.assert();
+        mock_open_orders.assert();
+    }
+
+    #[test]
+    fn timestamp_is_resynced_after_1021() {
+        // Far enough in the future that only a corrected timestamp starts with 4102444
+        let mock_time = mock("GET", "/api/v3/time")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"serverTime":4102444800000}"#)
+            .expect(1)
+            .create();
+        let mock_rejected = mock("GET", "/api/v3/openOrders")
+            .match_query(Matcher::Regex("timestamp=1".into()))
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"code":-1021,"msg":"Timestamp for this request is outside of the recvWindow."}"#)
+            .expect(1)
+            .create();
+        let mock_accepted = mock("GET", "/api/v3/openOrders")
+            .match_query(Matcher::Regex("timestamp=4102444".into()))
+            .with_header("content-type", "application/json")
+            .with_body("[]")
+            .expect(1)
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_time_sync_interval(None);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let orders = account.get_all_open_orders().unwrap();
+
+        mock_time.assert();
+        mock_rejected.assert();
+        mock_accepted.assert();
+        assert!(orders.is_empty());
+        assert!(account.client.time_sync().offset().unwrap() > 0);
+    }
+
+    #[test]
+    fn rejected_order_has_typed_code() {
+        let mock_order = mock("POST", "/api/v3/order")
+            .match_query(Matcher::Regex("symbol=BNBBTC".into()))
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"code":-2010,"msg":"Account has insufficient balance for requested action."}"#,
+            )
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let err = account.limit_buy("BNBBTC", 1, amount("0.1")).unwrap_err();
+
+        mock_order.assert();
+        assert_eq!(err.binance_code(), Some(BinanceErrorCode::NewOrderRejected));
+        assert!(!err.is_retryable());
+        assert!(!err.is_rate_limited());
+    }
+
+    #[test]
+    fn failed_cancel_replace_has_shared_code() {
+        let mock_cancel_replace = mock("POST", "/api/v3/order/cancelReplace")
+            .match_query(Matcher::Regex("symbol=BNBBTC".into()))
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"code":-2022,"msg":"Order cancel-replace failed."}"#)
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order = OrderRequest::new("BNBBTC", OrderSide::Buy, OrderType::Market)
+            .set_quantity("1".parse().unwrap());
+        let err = account
+            .cancel_replace_order(1, order, CancelReplaceMode::StopOnFailure)
+            .unwrap_err();
+
+        mock_cancel_replace.assert();
+        assert_eq!(
+            err.binance_code(),
+            Some(BinanceErrorCode::ReduceOnlyOrCancelReplaceRejected)
+        );
+        assert_eq!(
+            BinanceErrorCode::ReduceOnlyOrCancelReplaceRejected.code(),
+            -2022
+        );
+    }
+
+    #[test]
+    fn too_many_requests_is_rate_limited() {
+        let mock_book_ticker = mock("GET", "/api/v3/ticker/bookTicker")
+            .match_query(Matcher::UrlEncoded("symbol".into(), "LTCBTC".into()))
+            .with_status(429)
+            .with_header("content-type", "application/json")
+            .with_header("retry-after", "30")
+            .with_body(r#"{"code":-1003,"msg":"Too many requests."}"#)
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_retry_policy(RetryPolicy::none());
+        let market: Market = Binance::new_with_config(None, None, &config);
+        let err = market.get_book_ticker("LTCBTC").unwrap_err();
+
+        mock_book_ticker.assert();
+        match err.kind() {
+            ErrorKind::TooManyRequests(Some(response), _) => assert_eq!(response.code, -1003),
+            _ => panic!("Unexpected error: {}", err),
+        }
+        assert_eq!(err.binance_code(), Some(BinanceErrorCode::TooManyRequests));
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(30)));
+        assert!(err.is_rate_limited());
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn server_error_body_is_parsed() {
+        let mock_ping = mock("GET", "/api/v3/ping")
+            .with_status(503)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"code":-1001,"msg":"Internal error; unable to process your request. Please try again."}"#)
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_retry_policy(RetryPolicy::none());
+        let general: General = Binance::new_with_config(None, None, &config);
+        let err = general.ping().unwrap_err();
+
+        mock_ping.assert();
+        match err.kind() {
+            ErrorKind::ServerError(503, Some(_)) => {}
+            _ => panic!("Unexpected error: {}", err),
+        }
+        assert_eq!(err.binance_code(), Some(BinanceErrorCode::Disconnected));
+        assert!(err.is_retryable());
+        assert!(!err.is_rate_limited());
+    }
+}
@@ -0,0 +1,45 @@
+use binance::api::*;
+use binance::config::*;
+use binance::blocking::account::*;
+use binance::rust_decimal::Decimal;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_prices_round_trip_exactly() {
+        let mock_all_orders = mock("GET", "/api/v3/allOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("endTime=1499827400000&limit=10&recvWindow=1234&startTime=1499827300000&symbol=LTCBTC&timestamp=\\d+&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/all_orders.json")
+            .create();
+        let mock_test_order = mock("POST", "/api/v3/order/test")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("price=0.3&quantity=1.0&recvWindow=1234&side=BUY&symbol=LTCBTC&timeInForce=GTC&timestamp=\\d+&type=LIMIT&signature=.*".into()))
+            .with_body("{}")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let orders = account
+            .get_all_orders("LTCBTC", None, 1499827300000, 1499827400000, 10)
+            .unwrap();
+        assert_eq!(orders[0].price, Decimal::from_str("0.1").unwrap());
+
+        let qty = Decimal::from_str(&orders[0].orig_qty).unwrap();
+        let price = orders[0].price + Decimal::from_str("0.2").unwrap();
+        let order = OrderRequest::new("LTCBTC", OrderSide::Buy, OrderType::Limit)
+            .set_time_in_force(TimeInForce::GTC)
+            .set_quantity(qty)
+            .set_price(price);
+        account.test_order(order).unwrap();
+
+        mock_all_orders.assert();
+        mock_test_order.assert();
+    }
+}
@@ -1,6 +1,6 @@
 use binance::api::*;
 use binance::config::*;
-use binance::general::*;
+use binance::blocking::general::*;
 // use crate::account::*;
 // use crate::market::*;
 
@@ -0,0 +1,280 @@
+use binance::api::*;
+use binance::config::*;
+use binance::blocking::market::*;
+use binance::model::{Amount, AggTrades, DepthOrderBookEvent, KlineSummaries, KlineSummary, Trades};
+use binance::order_book::{LocalOrderBook, UpdateStatus};
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+
+    fn amount(value: &str) -> Amount {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn get_historical_trades() {
+        let mock_historical_trades = mock("GET", "/api/v3/historicalTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Exact("fromId=28457&limit=2&symbol=BNBBTC".into()))
+            .with_body_from_file("tests/mocks/market/historical_trades.json")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config);
+
+        let Trades::AllTrades(trades) = market.get_historical_trades("BNBBTC", 28457, 2).unwrap();
+        mock_historical_trades.assert();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].id, 28457);
+        assert_eq!(trades[0].price, amount("4.000001"));
+        assert_eq!(trades[1].quote_qty, amount("6.000003"));
+        assert!(trades[0].is_buyer_maker);
+        assert!(!trades[1].is_buyer_maker);
+    }
+
+    #[test]
+    fn get_agg_trades() {
+        let mock_agg_trades = mock("GET", "/api/v3/aggTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Exact(
+                "endTime=1498793709999&startTime=1498793709000&symbol=BNBBTC".into(),
+            ))
+            .with_body_from_file("tests/mocks/market/agg_trades.json")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config);
+
+        let AggTrades::AllAggTrades(trades) = market
+            .get_agg_trades("BNBBTC", None, 1_498_793_709_000, 1_498_793_709_999, None)
+            .unwrap();
+        mock_agg_trades.assert();
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].agg_id, 26129);
+        assert_eq!(trades[0].first_id, 27781);
+        assert_eq!(trades[0].time, 1_498_793_709_153);
+        assert!(trades[0].maker);
+        assert_eq!(trades[0].qty, amount("4.70443515"));
+    }
+
+    fn klines_page(first_open_time: u64, count: u64) -> String {
+        let rows: Vec<String> = (0..count)
+            .map(|i| {
+                let open_time = first_open_time + i * 60_000;
+                format!(
+                    "[{},\"1.0\",\"2.0\",\"0.5\",\"1.5\",\"10.0\",{},\"15.0\",3,\"5.0\",\"7.5\",\"0\"]",
+                    open_time,
+                    open_time + 59_999
+                )
+            })
+            .collect();
+        format!("[{}]", rows.join(","))
+    }
+
+    fn agg_trades_page(first_id: u64, first_time: u64, count: u64) -> String {
+        let rows: Vec<String> = (0..count)
+            .map(|i| {
+                format!(
+                    "{{\"a\":{},\"p\":\"1.0\",\"q\":\"2.0\",\"f\":{},\"l\":{},\"T\":{},\"m\":true,\"M\":true}}",
+                    first_id + i,
+                    first_id + i,
+                    first_id + i,
+                    first_time + i
+                )
+            })
+            .collect();
+        format!("[{}]", rows.join(","))
+    }
+
+    #[test]
+    fn get_klines_range() {
+        let start_time = 1_500_000_000_000;
+        let second_page = start_time + 1000 * 60_000;
+        let end_time = second_page + 60_000;
+
+        let mock_first_page = mock("GET", "/api/v3/klines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Exact(format!(
+                "endTime={}&interval=1m&limit=1000&startTime={}&symbol=BNBBTC",
+                end_time, start_time
+            )))
+            .with_body(klines_page(start_time, 1000))
+            .create();
+        let mock_second_page = mock("GET", "/api/v3/klines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Exact(format!(
+                "endTime={}&interval=1m&limit=1000&startTime={}&symbol=BNBBTC",
+                end_time, second_page
+            )))
+            .with_body(klines_page(second_page, 2))
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config);
+
+        let klines: Vec<_> = market
+            .get_klines_range("BNBBTC", KlineInterval::Minute1, start_time, end_time)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        mock_first_page.assert();
+        mock_second_page.assert();
+
+        assert_eq!(klines.len(), 1002);
+        assert_eq!(klines[0].open_time, start_time as i64);
+        assert_eq!(klines[1001].open_time, end_time as i64);
+    }
+
+    #[test]
+    fn get_agg_trades_range() {
+        let start_time = 1_500_000_000_000;
+        let end_time = start_time + 1000;
+
+        let mock_first_page = mock("GET", "/api/v3/aggTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Exact(format!(
+                "limit=1000&startTime={}&symbol=BNBBTC",
+                start_time
+            )))
+            .with_body(agg_trades_page(1, start_time, 1000))
+            .create();
+        // Continues from the last aggregate trade id, past the end of the range
+        let mock_second_page = mock("GET", "/api/v3/aggTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Exact(
+                "fromId=1001&limit=1000&symbol=BNBBTC".into(),
+            ))
+            .with_body(agg_trades_page(1001, start_time + 1000, 1000))
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config);
+
+        let trades: Vec<_> = market
+            .get_agg_trades_range("BNBBTC", start_time, end_time)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        mock_first_page.assert();
+        mock_second_page.assert();
+
+        assert_eq!(trades.len(), 1001);
+        assert_eq!(trades[1000].agg_id, 1001);
+        assert_eq!(trades[1000].time, end_time);
+    }
+
+    #[test]
+    fn get_klines() {
+        let mock_klines = mock("GET", "/api/v3/klines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Exact("interval=1w&limit=1&symbol=BNBBTC".into()))
+            .with_body_from_file("tests/mocks/market/klines.json")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config);
+
+        let KlineSummaries::AllKlineSummaries(klines) = market
+            .get_klines("BNBBTC", KlineInterval::Week1, 1, None, None)
+            .unwrap();
+        mock_klines.assert();
+
+        assert_eq!(klines.len(), 1);
+        assert_eq!(klines[0].open_time, 1_499_040_000_000);
+        assert_eq!(klines[0].close_time, 1_499_644_799_999);
+        assert_eq!(klines[0].number_of_trades, 308);
+        assert_eq!(klines[0].high, amount("0.8"));
+        assert_eq!(
+            klines[0].taker_buy_quote_asset_volume,
+            amount("28.46694368")
+        );
+
+        // Serialized back into the array Binance sends
+        let json = serde_json::to_string(&klines[0]).unwrap();
+        assert!(json.starts_with("[1499040000000,\""));
+        let kline: KlineSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(kline.close_time, klines[0].close_time);
+        assert_eq!(kline.number_of_trades, klines[0].number_of_trades);
+        assert_eq!(kline.high, klines[0].high);
+        assert_eq!(
+            kline.taker_buy_quote_asset_volume,
+            klines[0].taker_buy_quote_asset_volume
+        );
+    }
+
+    #[test]
+    fn get_klines_malformed_row() {
+        let mock_klines = mock("GET", "/api/v3/klines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Any)
+            .with_body(r#"[[1499040000000,"0.01634790","0.80000000"]]"#)
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config);
+
+        let result = market.get_klines("BNBBTC", KlineInterval::Minute1, None, None, None);
+        mock_klines.assert();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn kline_interval() {
+        for interval in KlineInterval::ALL.iter() {
+            assert_eq!(
+                interval.to_string().parse::<KlineInterval>().unwrap(),
+                *interval
+            );
+        }
+        assert!("2m".parse::<KlineInterval>().is_err());
+        assert_eq!(KlineInterval::Month1.to_string(), "1M");
+
+        assert_eq!(
+            KlineInterval::Minute15.duration(),
+            Some(Duration::from_secs(900))
+        );
+        assert_eq!(KlineInterval::Month1.duration(), None);
+        assert_eq!(
+            KlineInterval::Hour4.next_open_time(1_499_040_000_000),
+            1_499_054_400_000
+        );
+        // 2020-02-01 -> 2020-03-01 and 2020-12-01 -> 2021-01-01
+        assert_eq!(
+            KlineInterval::Month1.next_open_time(1_580_515_200_000),
+            1_583_020_800_000
+        );
+        assert_eq!(
+            KlineInterval::Month1.next_open_time(1_606_780_800_000),
+            1_609_459_200_000
+        );
+    }
+
+    #[test]
+    fn update_order_book() {
+        let mock_depth = mock("GET", "/api/v3/depth")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Exact("limit=1000&symbol=BNBBTC".into()))
+            .with_body_from_file("tests/mocks/order_book/spot_depth_snapshot.json")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config);
+
+        let mut book = LocalOrderBook::spot("BNBBTC");
+        let update: DepthOrderBookEvent = serde_json::from_str(
+            r#"{"e":"depthUpdate","E":1672515782236,"s":"BNBBTC","U":158,"u":162,"b":[["0.0025","1.0"]],"a":[]}"#,
+        )
+        .unwrap();
+        let status = market.update_order_book(&mut book, update).unwrap();
+        mock_depth.assert();
+
+        assert_eq!(status, UpdateStatus::Buffered);
+        assert!(book.is_synced());
+        assert_eq!(book.last_update_id(), 162);
+        assert_eq!(book.best_bid().unwrap().price, amount("0.0025"));
+    }
+}
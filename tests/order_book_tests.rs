@@ -0,0 +1,174 @@
+use binance::futures::model as futures_model;
+use binance::model::{Amount, DepthOrderBookEvent, OrderBook};
+use binance::order_book::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn amount(value: &str) -> Amount {
+        value.parse().unwrap()
+    }
+
+    fn load<T: serde::de::DeserializeOwned>(path: &str) -> T {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn spot_book() -> LocalOrderBook {
+        let mut book = LocalOrderBook::spot("bnbbtc");
+        let updates: Vec<DepthOrderBookEvent> =
+            load("tests/mocks/order_book/spot_depth_updates.json");
+        for update in updates {
+            assert_eq!(book.push(update), UpdateStatus::Buffered);
+        }
+        assert!(book.needs_snapshot());
+
+        let snapshot: OrderBook = load("tests/mocks/order_book/spot_depth_snapshot.json");
+        assert!(book.apply_snapshot(snapshot));
+        book
+    }
+
+    fn spot_update(first_update_id: u64, final_update_id: u64) -> DepthUpdate {
+        DepthUpdate {
+            symbol: "BNBBTC".into(),
+            first_update_id,
+            final_update_id,
+            previous_final_update_id: None,
+            bids: vec![PriceLevel {
+                price: amount("0.0021"),
+                qty: amount("1"),
+            }],
+            asks: vec![],
+        }
+    }
+
+    #[test]
+    fn spot_sync_from_buffered_updates() {
+        let book = spot_book();
+
+        assert!(book.is_synced());
+        assert!(!book.needs_snapshot());
+        assert_eq!(book.last_update_id(), 165);
+        assert_eq!(
+            book.best_bid(),
+            Some(PriceLevel {
+                price: amount("0.00245"),
+                qty: amount("4")
+            })
+        );
+        assert_eq!(
+            book.best_ask(),
+            Some(PriceLevel {
+                price: amount("0.0025"),
+                qty: amount("3")
+            })
+        );
+        assert!((book.spread().unwrap() - amount("0.00005")).abs() < amount("0.000000000001"));
+
+        // The stale update to 0.0024 was dropped, the next one applied
+        assert_eq!(book.bid_qty(amount("0.0024")), amount("12"));
+        assert_eq!(book.ask_qty(amount("0.0026")), amount("0"));
+        assert_eq!(book.bid_volume_to(amount("0.0023")), amount("21"));
+        assert_eq!(book.ask_volume_to(amount("0.0027")), amount("12"));
+
+        let bids: Vec<Amount> = book.bids().map(|level| level.price).collect();
+        assert_eq!(
+            bids,
+            vec![
+                amount("0.00245"),
+                amount("0.0024"),
+                amount("0.0023"),
+                amount("0.0022")
+            ]
+        );
+        let asks: Vec<Amount> = book.asks().map(|level| level.price).collect();
+        assert_eq!(asks, vec![amount("0.0025"), amount("0.0027")]);
+    }
+
+    #[test]
+    fn spot_gap_needs_new_snapshot() {
+        let mut book = spot_book();
+
+        assert_eq!(book.push(spot_update(160, 165)), UpdateStatus::Ignored);
+        assert_eq!(book.push(spot_update(166, 167)), UpdateStatus::Applied);
+        assert_eq!(book.push(spot_update(170, 171)), UpdateStatus::Gap);
+
+        assert!(!book.is_synced());
+        assert!(book.needs_snapshot());
+        assert_eq!(book.best_bid(), None);
+        assert_eq!(book.push(spot_update(172, 172)), UpdateStatus::Buffered);
+
+        let snapshot = DepthSnapshot {
+            last_update_id: 170,
+            bids: vec![PriceLevel {
+                price: amount("0.002"),
+                qty: amount("1"),
+            }],
+            asks: vec![],
+        };
+        assert!(book.apply_snapshot(snapshot));
+        assert_eq!(book.last_update_id(), 172);
+        assert_eq!(book.bid_volume_to(amount("0")), amount("2"));
+    }
+
+    #[test]
+    fn spot_snapshot_older_than_updates() {
+        let mut book = LocalOrderBook::spot("BNBBTC");
+        book.push(spot_update(200, 201));
+
+        let snapshot: OrderBook = load("tests/mocks/order_book/spot_depth_snapshot.json");
+        assert!(!book.apply_snapshot(snapshot));
+        assert!(book.needs_snapshot());
+        assert_eq!(book.best_ask(), None);
+    }
+
+    #[test]
+    fn futures_sync_and_gap() {
+        let mut book = LocalOrderBook::futures("BTCUSDT");
+        let updates: Vec<futures_model::DepthOrderBookEvent> =
+            load("tests/mocks/order_book/futures_depth_updates.json");
+        for update in updates {
+            book.push(update);
+        }
+
+        let snapshot: futures_model::OrderBook =
+            load("tests/mocks/order_book/futures_depth_snapshot.json");
+        assert!(book.apply_snapshot(snapshot));
+
+        assert_eq!(book.last_update_id(), 1005);
+        assert_eq!(book.bid_qty(amount("16500.1")), amount("0"));
+        assert_eq!(book.best_bid().unwrap().price, amount("16500"));
+        assert_eq!(
+            book.best_ask(),
+            Some(PriceLevel {
+                price: amount("16500.2"),
+                qty: amount("2.5")
+            })
+        );
+        assert_eq!(book.bid_volume_to(amount("16499.9")), amount("7"));
+
+        // pu must be the previous final update id
+        let next = |previous_final_update_id, final_update_id| DepthUpdate {
+            symbol: "BTCUSDT".into(),
+            first_update_id: previous_final_update_id + 1,
+            final_update_id,
+            previous_final_update_id: Some(previous_final_update_id),
+            bids: vec![],
+            asks: vec![],
+        };
+        assert_eq!(book.push(next(1005, 1008)), UpdateStatus::Applied);
+        assert_eq!(book.push(next(1009, 1010)), UpdateStatus::Gap);
+        assert!(book.needs_snapshot());
+    }
+
+    #[test]
+    fn ignores_other_symbols() {
+        let mut book = spot_book();
+
+        let mut update = spot_update(166, 167);
+        update.symbol = "ETHBTC".into();
+        assert_eq!(book.push(update), UpdateStatus::Ignored);
+        assert_eq!(book.last_update_id(), 165);
+    }
+}
@@ -0,0 +1,54 @@
+use binance::signer::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Example request from the Binance API documentation
+    const PAYLOAD: &str = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559";
+
+    #[test]
+    fn hmac_signature() {
+        let signer =
+            HmacSigner::new("NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j");
+
+        assert_eq!(
+            signer.sign(PAYLOAD).unwrap(),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+
+    // Key from RFC 8032 test 1, expected signature generated with `openssl pkeyutl -sign -rawin`
+    #[test]
+    fn ed25519_signature() {
+        let signer =
+            Ed25519Signer::from_pkcs8_pem(include_str!("mocks/signer/ed25519_private_key.pem"))
+                .unwrap();
+
+        assert_eq!(
+            signer.sign(PAYLOAD).unwrap(),
+            "3fhuDZ9nYMviDQ5OEtJBJS11jUZDTRzRQ%2BTQMarm%2BLErFiJvUiVPQjTzDoWZQe4miPX%2ByHk1v%2FZ7TWLYjIbmCA%3D%3D"
+        );
+    }
+
+    // Expected signature generated with `openssl dgst -sha256 -sign`
+    #[test]
+    fn rsa_signature() {
+        let signer =
+            RsaSigner::from_pkcs8_pem(include_str!("mocks/signer/rsa_private_key.pem")).unwrap();
+
+        assert_eq!(
+            signer.sign(PAYLOAD).unwrap(),
+            "LYl1ZdobM6nnOybuzKRLGWKfGc6%2FcZh7%2Fc6YwduCItHKzRbOSV%2FZvwfDuUrqOpz7XE%2BE6lCvjhMzxtwglfjhOy02iy%2FXN9WkxGDKDhTLx4a2R6I5T97NSa3iOrwMx4YItJK2mHilBEd%2BScghrj4BiwVdIUYL1Feqx%2BnNTPWJglbSD8L8pS0TB2kPRcWeKZ5TAK7mYuQOUIOROeIsE7cqCB2Dcn3hXDs%2FEBMVR%2FDavkSgXREOThT3kBduFIaO2JkA%2Bjiteuw0zfV9m%2BgQjCLhhn0Y1uWV1BECCFAz92yJMAt%2BlDIoU2oLzd1NArI9R41ga1R5UU1V75XJImElSwiMdA%3D%3D"
+        );
+    }
+
+    #[test]
+    fn invalid_keys_are_rejected() {
+        assert!(Ed25519Signer::from_pkcs8_pem("not a key").is_err());
+        assert!(
+            RsaSigner::from_pkcs8_pem(include_str!("mocks/signer/ed25519_private_key.pem"))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,880 @@
+// The websocket callbacks return the crate's `Result`, see the allow in lib.rs
+#![allow(clippy::result_large_err)]
+
+use binance::api::Binance;
+use binance::config::{Config, ReconnectPolicy};
+use binance::futures::websockets::*;
+use binance::model::{Amount, ExecutionType, KlineInterval, OrderStatus};
+use binance::futures::userstream::{FuturesUserDataSession, FuturesUserStream};
+use binance::userstream::{UserDataSession, UserStream};
+use binance::websockets::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use mockito::mock;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use tungstenite::Message;
+
+    fn amount(value: &str) -> Amount {
+        value.parse().unwrap()
+    }
+
+    static TRADE: &str = r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":12345,"p":"0.001","q":"100","b":88,"a":50,"T":1672515782136,"m":true,"M":true}"#;
+    static AGG_TRADE: &str = r#"{"e":"aggTrade","E":1672515782136,"s":"BTCUSDT","a":5933014,"p":"16500.10","q":"0.010","f":100,"l":105,"T":1672515782136,"m":true}"#;
+
+    // Serves each list of messages to one connection, then closes it
+    async fn serve(connections: Vec<Vec<Message>>) -> Config {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}/ws/", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            for messages in connections {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                for message in messages {
+                    socket.send(message).await.unwrap();
+                }
+                socket.close(None).await.unwrap();
+                while let Some(Ok(_)) = socket.next().await {}
+            }
+        });
+
+        Config::default().set_ws_endpoint(endpoint)
+    }
+
+    // Answers SUBSCRIBE, UNSUBSCRIBE, LIST_SUBSCRIPTIONS and SET_PROPERTY requests, sending a
+    // trade before each SUBSCRIBE response. The first connection is closed after
+    // `close_first_after` requests.
+    async fn serve_requests(close_first_after: Option<usize>) -> Config {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}/ws/", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let mut close_after = close_first_after;
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let close_after = close_after.take();
+                tokio::spawn(async move {
+                    let mut streams: Vec<String> = Vec::new();
+                    let mut requests = 0;
+                    while let Some(Ok(Message::Text(msg))) = socket.next().await {
+                        let request: serde_json::Value = serde_json::from_str(&msg).unwrap();
+                        let params: Vec<String> = request["params"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|param| param.as_str().map(String::from))
+                            .collect();
+                        let result = match request["method"].as_str().unwrap() {
+                            "SUBSCRIBE" if params.iter().any(|param| param == "invalid") => None,
+                            "SUBSCRIBE" => {
+                                socket.send(Message::Text(TRADE.into())).await.unwrap();
+                                streams.extend(params);
+                                Some(serde_json::Value::Null)
+                            }
+                            "UNSUBSCRIBE" => {
+                                streams.retain(|stream| !params.contains(stream));
+                                Some(serde_json::Value::Null)
+                            }
+                            "LIST_SUBSCRIPTIONS" => Some(streams.clone().into()),
+                            _ => Some(serde_json::Value::Null),
+                        };
+                        let response = match result {
+                            Some(result) => {
+                                serde_json::json!({"result": result, "id": request["id"]})
+                            }
+                            None => serde_json::json!({
+                                "error": {"code": 2, "msg": "Invalid request: invalid stream"},
+                                "id": request["id"]
+                            }),
+                        };
+                        socket
+                            .send(Message::Text(response.to_string()))
+                            .await
+                            .unwrap();
+
+                        requests += 1;
+                        if Some(requests) == close_after {
+                            socket.close(None).await.unwrap();
+                        }
+                    }
+                });
+            }
+        });
+
+        Config::default().set_ws_endpoint(endpoint)
+    }
+
+    // The blocking counterpart of `serve`
+    fn serve_blocking(connections: Vec<Vec<Message>>) -> Config {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("ws://{}/ws/", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for messages in connections {
+                let (stream, _) = listener.accept().unwrap();
+                let mut socket = tungstenite::accept(stream).unwrap();
+                for message in messages {
+                    socket.write_message(message).unwrap();
+                }
+                socket.close(None).unwrap();
+                while socket.read_message().is_ok() {}
+            }
+        });
+
+        Config::default().set_ws_endpoint(endpoint)
+    }
+
+    // Only the futures endpoint points at the mock, so a futures stream opened on the
+    // spot endpoint fails to connect
+    fn futures_config(config: Config) -> Config {
+        let host = config.ws_endpoint.trim_end_matches("/ws/").to_string();
+        Config::default()
+            .set_ws_endpoint("ws://127.0.0.1:1/ws/")
+            .set_futures_ws_endpoint(host)
+    }
+
+    fn reconnect_policy() -> ReconnectPolicy {
+        ReconnectPolicy::default()
+            .set_backoff(Duration::from_millis(10), Duration::from_millis(50))
+            .set_jitter(false)
+    }
+
+    fn event_name(event: &WebsocketEvent) -> &'static str {
+        match event {
+            WebsocketEvent::Trade(_) => "trade",
+            WebsocketEvent::Disconnected(_) => "disconnected",
+            WebsocketEvent::Reconnected => "reconnected",
+            _ => "other",
+        }
+    }
+
+    #[tokio::test]
+    async fn event_stream() {
+        let config = serve(vec![vec![
+            Message::Ping(vec![]),
+            Message::Text(TRADE.into()),
+            Message::Text("{\"result\":null,\"id\":1}".into()),
+            Message::Text("not json".into()),
+            Message::Text(format!(
+                "{{\"stream\":\"bnbbtc@trade\",\"data\":{}}}",
+                TRADE
+            )),
+        ]])
+        .await;
+
+        let mut events = EventStream::connect_with_config("bnbbtc@trade", &config)
+            .await
+            .unwrap();
+
+        match events.next().await {
+            Some(Ok(WebsocketEvent::Trade(trade))) => {
+                assert_eq!(trade.symbol, "BNBBTC");
+                assert_eq!(trade.trade_id, 12345);
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(matches!(events.next().await, Some(Err(_))));
+        assert!(matches!(
+            events.next().await,
+            Some(Ok(WebsocketEvent::Trade(_)))
+        ));
+        assert!(matches!(events.next().await, Some(Err(_))));
+        assert!(events.next().await.is_none());
+    }
+
+    fn fixture(path: &str) -> String {
+        std::fs::read_to_string(format!("tests/mocks/websockets/{}", path)).unwrap()
+    }
+
+    // Combines a fixture as a message of `stream`
+    fn combined(stream: &str, path: &str) -> String {
+        format!("{{\"stream\":\"{}\",\"data\":{}}}", stream, fixture(path))
+    }
+
+    #[tokio::test]
+    async fn spot_events() {
+        let messages = vec![
+            fixture("spot/account_update.json"),
+            fixture("spot/execution_report.json"),
+            fixture("spot/agg_trade.json"),
+            fixture("spot/trade.json"),
+            fixture("spot/day_ticker.json"),
+            fixture("spot/all_day_tickers.json"),
+            fixture("spot/kline.json"),
+            fixture("spot/partial_depth.json"),
+            fixture("spot/depth_update.json"),
+            fixture("spot/book_ticker.json"),
+            fixture("spot/unknown.json"),
+            combined("bnbbtc@depth5@100ms", "spot/partial_depth.json"),
+            combined("bnbusdt@bookTicker", "spot/book_ticker.json"),
+            combined("bnbbtc@aggTrade", "spot/agg_trade.json"),
+        ];
+        let config = serve(vec![messages.into_iter().map(Message::Text).collect()]).await;
+
+        let events: Vec<_> = EventStream::connect_with_config("bnbbtc@trade", &config)
+            .await
+            .unwrap()
+            .take(14)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        match &events[0] {
+            WebsocketEvent::AccountUpdate(account) => assert_eq!(account.balance.len(), 2),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[1] {
+            WebsocketEvent::OrderTrade(order) => assert_eq!(order.order_id, 4293153),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[2] {
+            WebsocketEvent::AggrTrades(trade) => assert_eq!(trade.aggregated_trade_id, 12345),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[3] {
+            WebsocketEvent::Trade(trade) => assert_eq!(trade.trade_id, 12345),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[4] {
+            WebsocketEvent::DayTicker(ticker) => assert_eq!(ticker.num_trades, 18151),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[5] {
+            WebsocketEvent::DayTickerAll(tickers) => assert_eq!(tickers.len(), 1),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[6] {
+            WebsocketEvent::Kline(kline) => assert_eq!(kline.kline.interval, "1m"),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[7] {
+            WebsocketEvent::OrderBook(order_book) => assert_eq!(order_book.last_update_id, 160),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[8] {
+            WebsocketEvent::DepthOrderBook(depth) => assert_eq!(depth.final_update_id, 160),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[9] {
+            WebsocketEvent::BookTicker(ticker) => assert_eq!(ticker.update_id, 400900217),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[10] {
+            WebsocketEvent::Unknown(event) => assert_eq!(event["e"], "avgPrice"),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(matches!(events[11], WebsocketEvent::OrderBook(_)));
+        assert!(matches!(events[12], WebsocketEvent::BookTicker(_)));
+        assert!(matches!(events[13], WebsocketEvent::AggrTrades(_)));
+    }
+
+    #[tokio::test]
+    async fn spot_user_data_events() {
+        let reports: Vec<serde_json::Value> =
+            serde_json::from_str(&fixture("spot/execution_reports.json")).unwrap();
+        let mut messages = vec![
+            fixture("spot/account_position.json"),
+            fixture("spot/balance_update.json"),
+            fixture("spot/list_status.json"),
+            fixture("spot/listen_key_expired.json"),
+        ];
+        messages.extend(reports.iter().map(|report| report.to_string()));
+        let config = serve(vec![messages.into_iter().map(Message::Text).collect()]).await;
+
+        let mut events = EventStream::connect_with_config("listen_key", &config)
+            .await
+            .unwrap()
+            .take(4 + reports.len())
+            .map(Result::unwrap);
+
+        match events.next().await {
+            Some(WebsocketEvent::AccountPosition(account)) => {
+                assert_eq!(account.balances[0].asset, "ETH")
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match events.next().await {
+            Some(WebsocketEvent::BalanceUpdate(balance)) => {
+                assert_eq!(balance.balance_delta, "100.00000000")
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match events.next().await {
+            Some(WebsocketEvent::ListStatus(list)) => {
+                assert_eq!(list.contingency_type, "OCO");
+                assert_eq!(list.orders.len(), 2);
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(matches!(
+            events.next().await,
+            Some(WebsocketEvent::ListenKeyExpired(_))
+        ));
+
+        let mut execution_types = Vec::new();
+        while let Some(event) = events.next().await {
+            match event {
+                WebsocketEvent::OrderTrade(order) => {
+                    if order.execution_type == ExecutionType::TradePrevention {
+                        assert_eq!(order.prevented_match_id, Some(8));
+                        assert_eq!(order.prevented_quantity.as_deref(), Some("0.50000000"));
+                    }
+                    execution_types.push(order.execution_type);
+                }
+                event => panic!("Unexpected event: {:?}", event),
+            }
+        }
+        assert_eq!(
+            execution_types,
+            vec![
+                ExecutionType::New,
+                ExecutionType::Canceled,
+                ExecutionType::Replaced,
+                ExecutionType::Rejected,
+                ExecutionType::Trade,
+                ExecutionType::Expired,
+                ExecutionType::TradePrevention
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn futures_events() {
+        let messages = vec![
+            fixture("futures/order_trade_update.json"),
+            fixture("futures/account_update.json"),
+            fixture("futures/account_config_update.json"),
+            fixture("futures/listen_key_expired.json"),
+            fixture("futures/agg_trade.json"),
+            fixture("futures/depth_update.json"),
+            fixture("futures/unknown.json"),
+            combined("btcusdt@depth@100ms", "futures/depth_update.json"),
+        ];
+        let config =
+            futures_config(serve(vec![messages.into_iter().map(Message::Text).collect()]).await);
+
+        let events: Vec<_> = FuturesEventStream::connect_with_config("btcusdt@aggTrade", &config)
+            .await
+            .unwrap()
+            .take(8)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        match &events[0] {
+            FuturesWebsocketEvent::OrderTrade(order) => assert_eq!(order.event.order_id, 8886774),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[1] {
+            FuturesWebsocketEvent::AccountUpdate(account) => {
+                assert_eq!(account.event.positions[0].symbol, "BTCUSDT")
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[2] {
+            FuturesWebsocketEvent::LeverageUpdate(leverage) => {
+                assert_eq!(leverage.event.position_amount, 25)
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(matches!(
+            events[3],
+            FuturesWebsocketEvent::ListenKeyExpired(_)
+        ));
+        match &events[4] {
+            FuturesWebsocketEvent::AggTrade(trade) => assert_eq!(trade.agg_id, 5933014),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[5] {
+            FuturesWebsocketEvent::DepthOrderBook(depth) => {
+                assert_eq!(depth.previous_final_update_id, 149)
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[6] {
+            FuturesWebsocketEvent::Unknown(event) => assert_eq!(event["e"], "MARGIN_CALL"),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(matches!(
+            events[7],
+            FuturesWebsocketEvent::DepthOrderBook(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn futures_market_events() {
+        let messages = vec![
+            fixture("futures/mark_price.json"),
+            format!("[{}]", fixture("futures/mark_price.json")),
+            fixture("futures/kline.json"),
+            fixture("futures/continuous_kline.json"),
+            fixture("futures/mini_ticker.json"),
+            format!("[{}]", fixture("futures/mini_ticker.json")),
+            fixture("futures/ticker.json"),
+            format!("[{}]", fixture("futures/ticker.json")),
+            fixture("futures/book_ticker.json"),
+            fixture("futures/force_order.json"),
+            fixture("futures/composite_index.json"),
+            fixture("futures/contract_info.json"),
+            combined("btcusdt@depth5@100ms", "futures/depth_update.json"),
+        ];
+        let config =
+            futures_config(serve(vec![messages.into_iter().map(Message::Text).collect()]).await);
+
+        let events: Vec<_> = FuturesEventStream::connect_with_config("btcusdt@markPrice", &config)
+            .await
+            .unwrap()
+            .take(13)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        match &events[0] {
+            FuturesWebsocketEvent::MarkPrice(mark_price) => {
+                assert_eq!(mark_price.funding_rate, amount("0.00038167"))
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[1] {
+            FuturesWebsocketEvent::AllMarkPrices(mark_prices) => assert_eq!(mark_prices.len(), 1),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[2] {
+            FuturesWebsocketEvent::Kline(kline) => {
+                assert_eq!(kline.kline.first_trade_id, 4254893011)
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[3] {
+            FuturesWebsocketEvent::ContinuousKline(kline) => {
+                assert_eq!(kline.contract_type, "PERPETUAL");
+                assert_eq!(kline.kline.number_of_trades, 543);
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(matches!(events[4], FuturesWebsocketEvent::MiniTicker(_)));
+        assert!(matches!(
+            events[5],
+            FuturesWebsocketEvent::AllMiniTickers(_)
+        ));
+        match &events[6] {
+            FuturesWebsocketEvent::Ticker(ticker) => assert_eq!(ticker.num_trades, 18151),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(matches!(events[7], FuturesWebsocketEvent::AllTickers(_)));
+        match &events[8] {
+            FuturesWebsocketEvent::BookTicker(ticker) => assert_eq!(ticker.update_id, 400900217),
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[9] {
+            FuturesWebsocketEvent::ForceOrder(liquidation) => {
+                assert_eq!(liquidation.order.order_status, OrderStatus::Filled)
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[10] {
+            FuturesWebsocketEvent::CompositeIndex(index) => {
+                assert_eq!(index.composition.len(), 2)
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[11] {
+            FuturesWebsocketEvent::ContractInfo(contract) => {
+                assert_eq!(contract.brackets[1].max_leverage, 20)
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(matches!(
+            events[12],
+            FuturesWebsocketEvent::DepthOrderBook(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn user_data_session() {
+        let start = mock("POST", "/api/v3/userDataStream")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(r#"{"listenKey":"pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1"}"#)
+            .expect(3)
+            .create();
+        let keep_alive = mock("PUT", "/api/v3/userDataStream")
+            .with_status(400)
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(r#"{"code":-1125,"msg":"This listenKey does not exist."}"#)
+            .expect(1)
+            .create();
+
+        // The key expires on the first connection, and is unknown after the second
+        let expired = r#"{"e":"listenKeyExpired","E":1576653824250,"listenKey":"pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1"}"#;
+        let config = serve(vec![
+            vec![Message::Text(TRADE.into()), Message::Text(expired.into())],
+            vec![Message::Text(TRADE.into())],
+            vec![Message::Text(TRADE.into())],
+        ])
+        .await
+        .set_rest_api_endpoint(mockito::server_url());
+
+        let user_stream: UserStream = Binance::new_with_config(None, None, &config);
+        let mut session =
+            UserDataSession::new(user_stream).set_reconnect_policy(reconnect_policy());
+        session.connect_with_config(&config).await.unwrap();
+
+        let events: Vec<_> = session
+            .take(7)
+            .map(|event| event_name(&event.unwrap()))
+            .collect()
+            .await;
+        assert_eq!(
+            events,
+            vec![
+                "trade",
+                "disconnected",
+                "reconnected",
+                "trade",
+                "disconnected",
+                "reconnected",
+                "trade"
+            ]
+        );
+        start.assert();
+        keep_alive.assert();
+    }
+
+    #[tokio::test]
+    async fn futures_user_data_session() {
+        let start = mock("POST", "/fapi/v1/listenKey")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"listenKey":"futures-listen-key"}"#)
+            .expect(1)
+            .create();
+
+        let order_update = fixture("futures/order_trade_update.json");
+        let config = futures_config(serve(vec![vec![Message::Text(order_update)]]).await)
+            .set_futures_rest_api_endpoint(mockito::server_url());
+
+        let user_stream: FuturesUserStream = Binance::new_with_config(None, None, &config);
+        let mut session = FuturesUserDataSession::new(user_stream);
+        session.connect_with_config(&config).await.unwrap();
+
+        match session.next().await {
+            Some(Ok(FuturesWebsocketEvent::OrderTrade(_))) => {}
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        start.assert();
+    }
+
+    #[tokio::test]
+    async fn futures_event_stream() {
+        let config = futures_config(serve(vec![vec![Message::Text(AGG_TRADE.into())]]).await);
+
+        let mut events = FuturesEventStream::connect_with_config("btcusdt@aggTrade", &config)
+            .await
+            .unwrap();
+
+        match events.next().await {
+            Some(Ok(FuturesWebsocketEvent::AggTrade(trade))) => {
+                assert_eq!(trade.symbol, "BTCUSDT");
+                assert_eq!(trade.agg_id, 5933014);
+                assert_eq!(trade.price, amount("16500.1"));
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        assert!(matches!(events.next().await, Some(Err(_))));
+        assert!(events.next().await.is_none());
+    }
+
+    #[test]
+    fn reconnect() {
+        let trade = || vec![Message::Text(TRADE.into())];
+        let config = serve_blocking(vec![trade(), trade()]);
+
+        let running = AtomicBool::new(true);
+        let mut events = Vec::new();
+        let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+            events.push(event_name(&event));
+            if events.iter().filter(|name| **name == "trade").count() == 2 {
+                running.store(false, Ordering::Relaxed);
+            }
+            Ok(())
+        })
+        .set_reconnect_policy(reconnect_policy());
+
+        web_socket
+            .connect_with_config("bnbbtc@trade", &config)
+            .unwrap();
+        web_socket.event_loop(&running).unwrap();
+        drop(web_socket);
+
+        assert_eq!(
+            events,
+            vec!["trade", "disconnected", "reconnected", "trade"]
+        );
+    }
+
+    #[test]
+    fn reconnect_gives_up() {
+        let config = serve_blocking(vec![vec![Message::Text(TRADE.into())]]);
+
+        let running = AtomicBool::new(true);
+        let mut events = Vec::new();
+        let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+            events.push(event_name(&event));
+            Ok(())
+        })
+        .set_reconnect_policy(reconnect_policy().set_max_attempts(3));
+
+        web_socket
+            .connect_with_config("bnbbtc@trade", &config)
+            .unwrap();
+        assert!(web_socket.event_loop(&running).is_err());
+        drop(web_socket);
+
+        assert_eq!(events, vec!["trade", "disconnected"]);
+    }
+
+    #[test]
+    fn disconnect_without_reconnect_policy() {
+        let config = serve_blocking(vec![vec![], vec![]]);
+
+        let running = AtomicBool::new(true);
+        let mut web_socket = WebSockets::new(|_event: WebsocketEvent| Ok(()));
+
+        web_socket
+            .connect_with_config("bnbbtc@trade", &config)
+            .unwrap();
+        assert!(web_socket.event_loop(&running).is_err());
+    }
+
+    #[tokio::test]
+    async fn async_event_loop() {
+        let config = serve(vec![vec![
+            Message::Text(TRADE.into()),
+            Message::Text("{\"result\":null,\"id\":1}".into()),
+            Message::Text(format!(
+                "{{\"stream\":\"bnbbtc@trade\",\"data\":{}}}",
+                TRADE
+            )),
+        ]])
+        .await;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let trades = Arc::new(Mutex::new(Vec::new()));
+        let mut web_socket = AsyncWebSockets::new(
+            |event: WebsocketEvent, (trades, running): (Arc<Mutex<Vec<u64>>>, Arc<AtomicBool>)| async move {
+                let mut trades = trades.lock().unwrap();
+                match event {
+                    WebsocketEvent::Trade(trade) => trades.push(trade.trade_id),
+                    event => panic!("Unexpected event: {:?}", event),
+                }
+                if trades.len() == 2 {
+                    running.store(false, Ordering::Relaxed);
+                }
+                Ok(())
+            },
+            (trades.clone(), running.clone()),
+        );
+
+        // The subscription only has to live for the call
+        let subscription = String::from("bnbbtc@trade");
+        web_socket
+            .connect_with_config(&subscription, &config)
+            .await
+            .unwrap();
+        drop(subscription);
+        web_socket.event_loop(&running).await.unwrap();
+
+        assert_eq!(*trades.lock().unwrap(), vec![12345, 12345]);
+    }
+
+    #[tokio::test]
+    async fn futures_reconnect() {
+        let agg_trade = || vec![Message::Text(AGG_TRADE.into())];
+        let config = futures_config(serve(vec![agg_trade(), agg_trade()]).await);
+
+        let running = Arc::new(AtomicBool::new(true));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut web_socket = FuturesWebSockets::new(
+            |event: FuturesWebsocketEvent,
+             (events, running): (Arc<Mutex<Vec<String>>>, Arc<AtomicBool>)| async move {
+                let mut events = events.lock().unwrap();
+                events.push(match event {
+                    FuturesWebsocketEvent::AggTrade(_) => "trade".to_string(),
+                    FuturesWebsocketEvent::Disconnected(_) => "disconnected".to_string(),
+                    FuturesWebsocketEvent::Reconnected => "reconnected".to_string(),
+                    event => format!("{:?}", event),
+                });
+                if events.iter().filter(|name| *name == "trade").count() == 2 {
+                    running.store(false, Ordering::Relaxed);
+                }
+                Ok(())
+            },
+            (events.clone(), running.clone()),
+        )
+        .set_reconnect_policy(reconnect_policy());
+
+        web_socket
+            .connect_with_config("btcusdt@aggTrade", &config)
+            .await
+            .unwrap();
+        web_socket.event_loop(&running).await.unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["trade", "disconnected", "reconnected", "trade"]
+        );
+    }
+
+    #[tokio::test]
+    async fn subscribe() {
+        let config = serve_requests(None).await;
+        let mut events = EventStream::connect_with_config("bnbbtc@trade", &config)
+            .await
+            .unwrap();
+
+        events
+            .subscribe(&["ethbtc@trade", "ltcbtc@trade"])
+            .await
+            .unwrap();
+        assert!(matches!(
+            events.next().await,
+            Some(Ok(WebsocketEvent::Trade(_)))
+        ));
+        assert_eq!(
+            events.list_subscriptions().await.unwrap(),
+            vec!["ethbtc@trade", "ltcbtc@trade"]
+        );
+
+        events.unsubscribe(&["ethbtc@trade"]).await.unwrap();
+        assert_eq!(
+            events.list_subscriptions().await.unwrap(),
+            vec!["ltcbtc@trade"]
+        );
+        events.set_combined(true).await.unwrap();
+
+        let error = events.subscribe(&["invalid"]).await.unwrap_err();
+        match error.kind() {
+            binance::errors::ErrorKind::BinanceError(response) => assert_eq!(response.code, 2),
+            kind => panic!("Unexpected error: {:?}", kind),
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribe_stream_limit() {
+        let config = futures_config(serve_requests(None).await);
+        let mut events = FuturesEventStream::connect_with_config("btcusdt@aggTrade", &config)
+            .await
+            .unwrap();
+
+        let streams: Vec<String> = (0..MAX_STREAMS)
+            .map(|i| format!("symbol{}@aggTrade", i))
+            .collect();
+        assert!(events.subscribe(&streams).await.is_err());
+        events.subscribe(&streams[1..]).await.unwrap();
+        assert_eq!(
+            events.list_subscriptions().await.unwrap().len(),
+            MAX_STREAMS - 1
+        );
+        assert!(events.subscribe(&["ethusdt@aggTrade"]).await.is_err());
+    }
+
+    #[test]
+    fn subscribe_rate_limit() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let config = runtime.block_on(serve_requests(None));
+
+        let mut web_socket = WebSockets::new(|_event: WebsocketEvent| Ok(()));
+        web_socket
+            .connect_with_config("bnbbtc@trade", &config)
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        for _ in 0..=MAX_MESSAGES_PER_SECOND {
+            web_socket.list_subscriptions().unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn reconnect_restores_subscriptions() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let config = runtime.block_on(serve_requests(Some(1)));
+
+        let running = AtomicBool::new(true);
+        let mut events = Vec::new();
+        let mut web_socket = WebSockets::new(|event: WebsocketEvent| {
+            events.push(event_name(&event));
+            if let WebsocketEvent::Reconnected = event {
+                running.store(false, Ordering::Relaxed);
+            }
+            Ok(())
+        })
+        .set_reconnect_policy(reconnect_policy());
+
+        web_socket
+            .connect_with_config("bnbbtc@trade", &config)
+            .unwrap();
+        web_socket.subscribe(&["ethbtc@trade"]).unwrap();
+        web_socket.event_loop(&running).unwrap();
+        assert_eq!(
+            web_socket.list_subscriptions().unwrap(),
+            vec!["ethbtc@trade"]
+        );
+        drop(web_socket);
+
+        assert_eq!(
+            events,
+            vec!["trade", "disconnected", "trade", "reconnected"]
+        );
+    }
+
+    #[test]
+    fn stream_names() {
+        let kline = StreamName::Kline("BNBBTC".into(), KlineInterval::Minute1);
+        assert_eq!(kline.spot_name().unwrap(), "bnbbtc@kline_1m");
+        assert_eq!(kline.futures_name().unwrap(), "bnbbtc@kline_1m");
+
+        let depth = StreamName::Depth("BTCUSDT".into(), UpdateSpeed::Default);
+        assert_eq!(depth.spot_name().unwrap(), "btcusdt@depth");
+        assert_eq!(depth.futures_name().unwrap(), "btcusdt@depth");
+        let depth = StreamName::PartialDepth("BTCUSDT".into(), 10, UpdateSpeed::Millis100);
+        assert_eq!(depth.spot_name().unwrap(), "btcusdt@depth10@100ms");
+        let depth = StreamName::PartialDepth("BTCUSDT".into(), 5, UpdateSpeed::Millis500);
+        assert_eq!(depth.futures_name().unwrap(), "btcusdt@depth5@500ms");
+        assert!(depth.spot_name().is_err());
+        assert!(
+            StreamName::PartialDepth("BTCUSDT".into(), 15, UpdateSpeed::Default)
+                .spot_name()
+                .is_err()
+        );
+
+        let mark_prices = StreamName::AllMarkPrices(UpdateSpeed::Millis1000);
+        assert_eq!(mark_prices.futures_name().unwrap(), "!markPrice@arr@1s");
+        assert!(mark_prices.spot_name().is_err());
+
+        let continuous_kline = StreamName::ContinuousKline(
+            "BTCUSDT".into(),
+            ContractType::Perpetual,
+            KlineInterval::Hour1,
+        );
+        assert_eq!(
+            continuous_kline.futures_name().unwrap(),
+            "btcusdt_perpetual@continuousKline_1h"
+        );
+        assert!(StreamName::Trade("BNBBTC".into()).futures_name().is_err());
+        assert_eq!(
+            StreamName::ContractInfo.futures_name().unwrap(),
+            "!contractInfo"
+        );
+        assert!(StreamName::CompositeIndex("DEFIUSDT".into())
+            .spot_name()
+            .is_err());
+        assert_eq!(StreamName::AllTickers.spot_name().unwrap(), "!ticker@arr");
+    }
+}